@@ -0,0 +1,49 @@
+use std::fmt;
+use std::io;
+
+
+/// Everything that can go wrong while generating SQL.
+#[derive(Debug)]
+pub enum Error {
+    /// The underlying writer failed.
+    Io(io::Error),
+    /// The underlying formatter failed.
+    Fmt(fmt::Error),
+    /// The definition cannot be expressed as valid SQL.
+    InvalidSchema(String),
+    /// The SQL handed to a parser is not something this crate can read back.
+    Parse(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "failed to write SQL: {}", err),
+            Self::Fmt(err) => write!(f, "failed to format SQL: {}", err),
+            Self::InvalidSchema(reason) => write!(f, "invalid schema: {}", reason),
+            Self::Parse(reason) => write!(f, "failed to parse SQL: {}", reason),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(err) => Some(err),
+            Self::Fmt(err) => Some(err),
+            Self::InvalidSchema(_) | Self::Parse(_) => None,
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(err: io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+impl From<fmt::Error> for Error {
+    fn from(err: fmt::Error) -> Self {
+        Self::Fmt(err)
+    }
+}
@@ -1,3 +1,7 @@
+pub mod error;
+pub mod migration;
+pub mod schema;
 pub mod table;
 
-
+pub use error::Error;
+pub use schema::Schema;
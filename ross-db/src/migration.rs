@@ -0,0 +1,60 @@
+use diesel::pg::Pg;
+
+use crate::Error;
+use crate::table::fields::{IntoSql, TableDefn};
+
+
+/// A pair of scripts that apply a change and revert it, in the spirit of diesel's
+/// `up.sql`/`down.sql` migration files.
+#[derive(Default)]
+pub struct Migration {
+    pub up: Vec<Box<dyn IntoSql<Pg>>>,
+    pub down: Vec<Box<dyn IntoSql<Pg>>>,
+}
+
+impl Migration {
+    /// Create `table` on the way up and drop it on the way down.
+    pub fn create_table(table: &TableDefn) -> Self {
+        Self {
+            up: vec![Box::new(table.clone())],
+            down: vec![Box::new(table.drop_table())],
+        }
+    }
+
+    /// The up and down scripts, each statement terminated by a semicolon on its own line.
+    pub fn render(&self) -> Result<(String, String), Error> {
+        Ok((render_statements(&self.up)?, render_statements(&self.down)?))
+    }
+}
+
+fn render_statements(statements: &[Box<dyn IntoSql<Pg>>]) -> Result<String, Error> {
+    let mut sql = String::new();
+    for statement in statements {
+        statement.into_sql_fmt(&mut sql)?;
+        sql.push_str(";\n");
+    }
+    Ok(sql)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::table::alter::AlterTable;
+    use crate::table::posts;
+
+    #[test]
+    fn create_drop_pair() {
+        let (up, down) = Migration::create_table(&posts()).render().unwrap();
+        assert_eq!(up, "CREATE TABLE posts (\n\tid SERIAL PRIMARY KEY,\n\tauthor_id INTEGER,\n\tslug TEXT,\n\ttitle TEXT NOT NULL,\n\tpublished BOOLEAN NOT NULL\n);\n");
+        assert_eq!(down, "DROP TABLE posts;\n");
+    }
+
+    #[test]
+    fn multiple_statements() {
+        let mut migration = Migration::create_table(&posts());
+        migration.up.push(Box::new(AlterTable::new("posts").drop_column("legacy", false)));
+        let (up, _) = migration.render().unwrap();
+        assert_eq!(up, "CREATE TABLE posts (\n\tid SERIAL PRIMARY KEY,\n\tauthor_id INTEGER,\n\tslug TEXT,\n\ttitle TEXT NOT NULL,\n\tpublished BOOLEAN NOT NULL\n);\nALTER TABLE posts DROP COLUMN legacy;\n");
+    }
+}
@@ -0,0 +1,300 @@
+use std::fmt;
+
+use diesel::pg::Pg;
+
+use crate::Error;
+use crate::table::enums::EnumType;
+use crate::table::fields::{IntoSql, SqlWrite, TableDefn};
+use crate::table::format::FormatOptions;
+use crate::table::index::Index;
+
+
+/// A batch of tables, and the types and indexes that go with them, rendered together as one script.
+#[derive(Debug, Default)]
+pub struct Schema {
+    /// Created before the tables, so columns can use them.
+    pub types: Vec<EnumType>,
+    pub tables: Vec<TableDefn>,
+    /// Created after the tables they cover.
+    pub indexes: Vec<Index>,
+}
+
+impl Schema {
+    pub fn new(tables: Vec<TableDefn>) -> Self {
+        Self {
+            tables,
+            ..Default::default()
+        }
+    }
+
+    /// The tables ordered so that every table comes after the tables its foreign keys
+    /// reference. Tables without such a dependency keep their relative order.
+    pub fn sorted_tables(&self) -> Result<Vec<&TableDefn>, Error> {
+        let mut remaining: Vec<&TableDefn> = self.tables.iter().collect();
+        let mut sorted: Vec<&TableDefn> = Vec::with_capacity(remaining.len());
+
+        while !remaining.is_empty() {
+            let ready = remaining.iter().position(|table| {
+                table.referenced_tables().all(|referenced| {
+                    is_table(table, referenced)
+                    || sorted.iter().any(|done| is_table(done, referenced))
+                    || !remaining.iter().any(|pending| is_table(pending, referenced))
+                })
+            });
+            match ready {
+                Some(index) => sorted.push(remaining.remove(index)),
+                None => {
+                    let names: Vec<&str> = remaining.iter().map(|table| table.options.name.as_str()).collect();
+                    return Err(Error::InvalidSchema(format!("foreign keys form a cycle between {}", names.join(", "))));
+                },
+            }
+        }
+
+        Ok(sorted)
+    }
+}
+
+impl TableDefn {
+    fn referenced_tables(&self) -> impl Iterator<Item = (Option<&str>, &str)> {
+        self
+        .fields
+        .iter()
+        .filter_map(|field| field.options.references.as_ref())
+        .map(|references| (references.schema.as_deref(), references.table.as_str()))
+        .chain(self.options.foreign_keys.iter().map(|foreign_key| (foreign_key.schema.as_deref(), foreign_key.table.as_str())))
+    }
+}
+
+fn is_table(table: &TableDefn, (schema, name): (Option<&str>, &str)) -> bool {
+    name == table.options.name && (schema.is_none() || schema == table.options.schema.as_deref())
+}
+
+fn qualified_name(schema: &Option<String>, name: &str) -> String {
+    match schema {
+        Some(schema) => format!("{}.{}", schema, name),
+        None => name.to_string(),
+    }
+}
+
+fn separator(writer: &mut dyn fmt::Write, statements: &mut usize) -> Result<usize, Error> {
+    *statements += 1;
+    if *statements > 1 {
+        writer.write_sql(";\n\n")
+    } else {
+        Ok(0)
+    }
+}
+
+impl Schema {
+    /// Render the types, every table with the given layout in dependency order, and then the indexes.
+    pub fn into_sql_with_options(&self, writer: &mut dyn fmt::Write, format: &FormatOptions) -> Result<usize, Error> {
+        let tables = self.sorted_tables()?;
+
+        let mut total_bytes = 0;
+        let mut statements = 0;
+        for enum_type in &self.types {
+            total_bytes += separator(writer, &mut statements)?;
+            total_bytes += enum_type.into_sql_with_options(writer, format)?;
+        }
+        for table in tables {
+            total_bytes += separator(writer, &mut statements)?;
+            total_bytes += table.into_sql_with_options(writer, format)?;
+        }
+        for index in &self.indexes {
+            total_bytes += separator(writer, &mut statements)?;
+            total_bytes += index.into_sql_with_options(writer, format)?;
+        }
+        Ok(total_bytes)
+    }
+
+    /// Each object's statement on its own as `(name, sql)`, names schema-qualified where set.
+    /// Types come first, then tables in dependency order, then indexes, so writing one
+    /// migration file per entry in this order applies cleanly.
+    ///
+    /// # Panics
+    ///
+    /// If the schema cannot be rendered, e.g. its foreign keys form a cycle;
+    /// see [`Schema::try_into_sql_files`] to handle that instead.
+    pub fn into_sql_files(&self) -> Vec<(String, String)> {
+        match self.try_into_sql_files() {
+            Ok(files) => files,
+            Err(error) => panic!("cannot render schema: {}", error),
+        }
+    }
+
+    /// Like [`Schema::into_sql_files`], returning the error instead of panicking.
+    pub fn try_into_sql_files(&self) -> Result<Vec<(String, String)>, Error> {
+        let mut files = Vec::with_capacity(self.types.len() + self.tables.len() + self.indexes.len());
+        for enum_type in &self.types {
+            let (sql, _) = IntoSql::<Pg>::into_sql_str(enum_type)?;
+            files.push((qualified_name(&enum_type.schema, &enum_type.name), sql));
+        }
+        for table in self.sorted_tables()? {
+            let (sql, _) = IntoSql::<Pg>::into_sql_str(table)?;
+            files.push((qualified_name(&table.options.schema, &table.options.name), sql));
+        }
+        for index in &self.indexes {
+            let (sql, _) = IntoSql::<Pg>::into_sql_str(index)?;
+            files.push((index.name.clone(), sql));
+        }
+        Ok(files)
+    }
+}
+
+impl IntoSql<Pg> for Schema {
+    fn into_sql_fmt(&self, writer: &mut dyn fmt::Write) -> Result<usize, Error> {
+        self.into_sql_with_options(writer, &FormatOptions::default())
+    }
+}
+
+
+
+/// A `CREATE SCHEMA` statement, the namespace that schema-qualified tables live in.
+#[derive(Debug, Clone, Default)]
+pub struct SchemaDefn {
+    pub name: String,
+    pub if_not_exists: bool,
+    /// The role that will own the schema.
+    pub authorization: Option<String>,
+}
+
+impl IntoSql<Pg> for SchemaDefn {
+    fn into_sql_fmt(&self, writer: &mut dyn fmt::Write) -> Result<usize, Error> {
+        let mut total_bytes = 0;
+        total_bytes += writer.write_sql("CREATE SCHEMA ")?;
+        if self.if_not_exists {
+            total_bytes += writer.write_sql("IF NOT EXISTS ")?;
+        }
+        total_bytes += writer.write_sql(&self.name)?;
+        if let Some(authorization) = &self.authorization {
+            total_bytes += writer.write_sql(" AUTHORIZATION ")?;
+            total_bytes += writer.write_sql(authorization)?;
+        }
+        Ok(total_bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::table::fields::Field;
+
+    fn table(name: &str, references: Option<&str>) -> TableDefn {
+        let table = TableDefn::builder(name).column("id", Field::Serial, |c| c.primary_key());
+        match references {
+            Some(referenced) => table.column(format!("{}_id", referenced), Field::Integer, |c| c.references(referenced, "id")),
+            None => table,
+        }.build()
+    }
+
+    #[test]
+    fn referenced_tables_come_first() {
+        let schema = Schema::new(vec![table("posts", Some("users")), table("users", None)]);
+        let (observed, _) = IntoSql::<Pg>::into_sql_str(&schema).unwrap();
+        let expected = "CREATE TABLE users (\n\tid SERIAL PRIMARY KEY\n);\n\nCREATE TABLE posts (\n\tid SERIAL PRIMARY KEY,\n\tusers_id INTEGER REFERENCES users(id)\n)";
+        assert_eq!(observed, expected);
+    }
+
+    #[test]
+    fn independent_tables_keep_their_order() {
+        let schema = Schema::new(vec![table("tags", None), table("posts", Some("users")), table("users", Some("users"))]);
+        let names: Vec<&str> = schema.sorted_tables().unwrap().iter().map(|table| table.options.name.as_str()).collect();
+        assert_eq!(names, ["tags", "users", "posts"]);
+    }
+
+    #[test]
+    fn references_match_the_schema() {
+        let mut audit_users = table("users", None);
+        audit_users.options.schema = Some("audit".to_string());
+        let mut auth_users = table("users", None);
+        auth_users.options.schema = Some("auth".to_string());
+        let mut posts = table("posts", Some("users"));
+        posts.fields[1].options.references.as_mut().unwrap().schema = Some("auth".to_string());
+
+        let schema = Schema::new(vec![posts, audit_users, auth_users]);
+        let names: Vec<_> = schema.sorted_tables().unwrap().iter().map(|table| table.options.schema.clone()).collect();
+        assert_eq!(names, [Some("audit".to_string()), Some("auth".to_string()), None]);
+    }
+
+    #[test]
+    fn foreign_key_cycle() {
+        let schema = Schema::new(vec![table("posts", Some("users")), table("users", Some("posts"))]);
+        assert!(matches!(IntoSql::<Pg>::into_sql_str(&schema), Err(Error::InvalidSchema(_))));
+    }
+
+    #[test]
+    fn two_space_indentation() {
+        let schema = Schema::new(vec![table("posts", Some("users")), table("users", None)]);
+        let mut observed = String::new();
+        schema.into_sql_with_options(&mut observed, &FormatOptions::with_indent("  ")).unwrap();
+        let expected = "CREATE TABLE users (\n  id SERIAL PRIMARY KEY\n);\n\nCREATE TABLE posts (\n  id SERIAL PRIMARY KEY,\n  users_id INTEGER REFERENCES users(id)\n)";
+        assert_eq!(observed, expected);
+    }
+
+    #[test]
+    fn lowercase_keywords() {
+        use crate::table::format::KeywordCase;
+
+        let users = table("users", None);
+        let schema = Schema {
+            types: vec![EnumType::new("mood", ["sad", "ok"])],
+            indexes: vec![Index { unique: true, ..users.index("users_id_key", ["id"]) }],
+            tables: vec![users],
+        };
+        let mut observed = String::new();
+        let format = FormatOptions { keyword_case: KeywordCase::Lower, ..Default::default() };
+        schema.into_sql_with_options(&mut observed, &format).unwrap();
+        let expected = concat!(
+            "create type mood as enum ('sad', 'ok');\n\n",
+            "create table users (\n\tid serial primary key\n);\n\n",
+            "create unique index users_id_key on users (id)",
+        );
+        assert_eq!(observed, expected);
+    }
+
+    #[test]
+    fn one_file_per_object() {
+        let mut users = table("users", None);
+        users.options.schema = Some("auth".to_string());
+        let posts = table("posts", Some("users"));
+        let schema = Schema {
+            types: vec![EnumType::new("mood", ["sad", "ok"])],
+            indexes: vec![posts.index("posts_users_id_idx", ["users_id"])],
+            tables: vec![posts, users],
+        };
+        let files = schema.into_sql_files();
+        let names: Vec<&str> = files.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(names, ["mood", "auth.users", "posts", "posts_users_id_idx"]);
+        assert_eq!(files[0].1, "CREATE TYPE mood AS ENUM ('sad', 'ok')");
+        assert_eq!(files[1].1, "CREATE TABLE auth.users (\n\tid SERIAL PRIMARY KEY\n)");
+        assert!(files[2].1.starts_with("CREATE TABLE posts ("));
+        assert!(files[3].1.starts_with("CREATE INDEX posts_users_id_idx ON posts"));
+
+        let (script, _) = IntoSql::<Pg>::into_sql_str(&schema).unwrap();
+        assert_eq!(script, files.into_iter().map(|(_, sql)| sql).collect::<Vec<_>>().join(";\n\n"));
+    }
+
+    #[test]
+    fn files_of_a_foreign_key_cycle() {
+        let schema = Schema::new(vec![table("posts", Some("users")), table("users", Some("posts"))]);
+        assert!(matches!(schema.try_into_sql_files(), Err(Error::InvalidSchema(_))));
+    }
+
+    #[test]
+    fn create_schema() {
+        let auth = SchemaDefn { name: "auth".to_string(), ..Default::default() };
+        let (observed, _) = IntoSql::<Pg>::into_sql_str(&auth).unwrap();
+        assert_eq!(observed, "CREATE SCHEMA auth");
+    }
+
+    #[test]
+    fn create_schema_full() {
+        let auth = SchemaDefn {
+            name: "auth".to_string(),
+            if_not_exists: true,
+            authorization: Some("owner".to_string()),
+        };
+        let (observed, _) = IntoSql::<Pg>::into_sql_str(&auth).unwrap();
+        assert_eq!(observed, "CREATE SCHEMA IF NOT EXISTS auth AUTHORIZATION owner");
+    }
+}
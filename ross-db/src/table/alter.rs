@@ -0,0 +1,439 @@
+use std::fmt;
+
+use diesel::pg::Pg;
+
+use crate::Error;
+use crate::table::fields::{Field, IntoSql, SqlWrite, TableDefn, TableField};
+use crate::table::format::FormatOptions;
+use crate::table::quote;
+
+
+/// A single change applied by an [`AlterTable`] statement.
+#[derive(Debug)]
+#[allow(clippy::large_enum_variant)]
+pub enum AlterAction {
+    AddColumn {
+        field: TableField,
+        /// Render `ADD COLUMN IF NOT EXISTS`, so re-running the migration is harmless.
+        if_not_exists: bool,
+    },
+    DropColumn {
+        name: String,
+        cascade: bool,
+    },
+    AlterColumnType {
+        name: String,
+        kind: Field,
+    },
+    SetNotNull {
+        name: String,
+    },
+    DropNotNull {
+        name: String,
+    },
+    /// `expression` is written verbatim after `SET DEFAULT`.
+    SetDefault {
+        name: String,
+        expression: String,
+    },
+    DropDefault {
+        name: String,
+    },
+    /// Postgres only allows a rename as the sole action of its `ALTER TABLE`.
+    RenameColumn {
+        from: String,
+        to: String,
+    },
+}
+
+impl AlterAction {
+    fn combinable(&self) -> bool {
+        !matches!(self, Self::RenameColumn { .. })
+    }
+
+    fn write_pg(&self, writer: &mut dyn fmt::Write, quote_identifiers: bool, format: &FormatOptions) -> Result<usize, Error> {
+        let quote = |name: &str| quote::identifier(name, quote_identifiers).into_owned();
+
+        let mut total_bytes = 0;
+        match self {
+            Self::AddColumn { field, if_not_exists } => {
+                total_bytes += format.keyword(writer, "ADD COLUMN ")?;
+                if *if_not_exists {
+                    total_bytes += format.keyword(writer, "IF NOT EXISTS ")?;
+                }
+                total_bytes += field.write_pg(writer, true, quote_identifiers, format)?;
+            },
+            Self::DropColumn { name, cascade } => {
+                total_bytes += format.keyword(writer, "DROP COLUMN ")?;
+                total_bytes += writer.write_sql(&quote(name))?;
+                if *cascade {
+                    total_bytes += format.keyword(writer, " CASCADE")?;
+                }
+            },
+            Self::AlterColumnType { name, kind } => {
+                total_bytes += writer.write_sql("ALTER COLUMN ")?;
+                total_bytes += writer.write_sql(&quote(name))?;
+                total_bytes += format.keyword(writer, " TYPE ")?;
+                total_bytes += kind.write_pg(writer, quote_identifiers, format)?;
+            },
+            Self::SetNotNull { name } => {
+                total_bytes += writer.write_sql("ALTER COLUMN ")?;
+                total_bytes += writer.write_sql(&quote(name))?;
+                total_bytes += format.keyword(writer, " SET NOT NULL")?;
+            },
+            Self::DropNotNull { name } => {
+                total_bytes += writer.write_sql("ALTER COLUMN ")?;
+                total_bytes += writer.write_sql(&quote(name))?;
+                total_bytes += format.keyword(writer, " DROP NOT NULL")?;
+            },
+            Self::SetDefault { name, expression } => {
+                total_bytes += writer.write_sql("ALTER COLUMN ")?;
+                total_bytes += writer.write_sql(&quote(name))?;
+                total_bytes += format.keyword(writer, " SET DEFAULT ")?;
+                total_bytes += writer.write_sql(expression)?;
+            },
+            Self::DropDefault { name } => {
+                total_bytes += writer.write_sql("ALTER COLUMN ")?;
+                total_bytes += writer.write_sql(&quote(name))?;
+                total_bytes += format.keyword(writer, " DROP DEFAULT")?;
+            },
+            Self::RenameColumn { from, to } => {
+                total_bytes += format.keyword(writer, "RENAME COLUMN ")?;
+                total_bytes += writer.write_sql(&quote(from))?;
+                total_bytes += format.keyword(writer, " TO ")?;
+                total_bytes += writer.write_sql(&quote(to))?;
+            },
+        }
+        Ok(total_bytes)
+    }
+}
+
+impl IntoSql<Pg> for AlterAction {
+    fn into_sql_fmt(&self, writer: &mut dyn fmt::Write) -> Result<usize, Error> {
+        self.write_pg(writer, false, &FormatOptions::default())
+    }
+}
+
+
+/// Builds `ALTER TABLE` statements out of one or more [`AlterAction`]s.
+///
+/// Consecutive actions share a statement; those Postgres requires on their own,
+/// like renames, get a separate one, and statements are joined by `;\n`.
+#[derive(Debug)]
+pub struct AlterTable {
+    /// Written verbatim, see [`TableDefn::alter_table`] for a schema-qualified, quoted name.
+    pub name: String,
+    pub actions: Vec<AlterAction>,
+    /// Double-quote column names that are reserved words or contain special characters.
+    pub quote_identifiers: bool,
+}
+
+impl AlterTable {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            actions: Vec::new(),
+            quote_identifiers: false,
+        }
+    }
+
+    pub fn add_column(mut self, field: TableField) -> Self {
+        self.actions.push(AlterAction::AddColumn { field, if_not_exists: false });
+        self
+    }
+
+    pub fn add_column_if_not_exists(mut self, field: TableField) -> Self {
+        self.actions.push(AlterAction::AddColumn { field, if_not_exists: true });
+        self
+    }
+
+    pub fn drop_column(mut self, name: impl Into<String>, cascade: bool) -> Self {
+        self.actions.push(AlterAction::DropColumn { name: name.into(), cascade });
+        self
+    }
+
+    pub fn rename_column(mut self, from: impl Into<String>, to: impl Into<String>) -> Self {
+        self.actions.push(AlterAction::RenameColumn { from: from.into(), to: to.into() });
+        self
+    }
+
+    pub fn alter_column_type(mut self, name: impl Into<String>, kind: Field) -> Self {
+        self.actions.push(AlterAction::AlterColumnType { name: name.into(), kind });
+        self
+    }
+
+    pub fn set_not_null(mut self, name: impl Into<String>) -> Self {
+        self.actions.push(AlterAction::SetNotNull { name: name.into() });
+        self
+    }
+
+    pub fn drop_not_null(mut self, name: impl Into<String>) -> Self {
+        self.actions.push(AlterAction::DropNotNull { name: name.into() });
+        self
+    }
+
+    pub fn set_default(mut self, name: impl Into<String>, expression: impl Into<String>) -> Self {
+        self.actions.push(AlterAction::SetDefault { name: name.into(), expression: expression.into() });
+        self
+    }
+
+    pub fn drop_default(mut self, name: impl Into<String>) -> Self {
+        self.actions.push(AlterAction::DropDefault { name: name.into() });
+        self
+    }
+}
+
+impl TableDefn {
+    /// An `ALTER TABLE` of this table with no actions yet, named and quoting columns the way
+    /// its `CREATE TABLE` does.
+    pub fn alter_table(&self) -> AlterTable {
+        AlterTable {
+            name: self.quoted_name().into_owned(),
+            actions: Vec::new(),
+            quote_identifiers: self.options.quote_identifiers,
+        }
+    }
+
+    /// The actions that migrate this table to `other`, matching columns by name.
+    ///
+    /// New columns and type changes come first, in the column order of `other`,
+    /// followed by the columns that no longer exist.
+    pub fn diff(&self, other: &TableDefn) -> Vec<AlterAction> {
+        let mut actions = Vec::new();
+        for field in &other.fields {
+            match self.column_kind(&field.options.name) {
+                None => actions.push(AlterAction::AddColumn { field: field.clone(), if_not_exists: false }),
+                Some(kind) if *kind != field.kind => actions.push(AlterAction::AlterColumnType {
+                    name: field.options.name.clone(),
+                    kind: field.kind.clone(),
+                }),
+                Some(_) => {},
+            }
+        }
+        for field in &self.fields {
+            if other.column_kind(&field.options.name).is_none() {
+                actions.push(AlterAction::DropColumn { name: field.options.name.clone(), cascade: false });
+            }
+        }
+        actions
+    }
+
+    fn column_kind(&self, name: &str) -> Option<&Field> {
+        self.fields.iter().find(|field| field.options.name == name).map(|field| &field.kind)
+    }
+}
+
+impl AlterTable {
+    /// Render the statements, spelling keywords and column types as `format` asks.
+    pub fn into_sql_with_options(&self, writer: &mut dyn fmt::Write, format: &FormatOptions) -> Result<usize, Error> {
+        if self.actions.is_empty() {
+            return Err(Error::InvalidSchema(format!("ALTER TABLE {} has nothing to alter", self.name)));
+        }
+
+        let mut total_bytes = 0;
+        let mut previous: Option<&AlterAction> = None;
+        for action in &self.actions {
+            match previous {
+                Some(previous) if previous.combinable() && action.combinable() => {
+                    total_bytes += writer.write_sql(", ")?;
+                },
+                _ => {
+                    if previous.is_some() {
+                        total_bytes += writer.write_sql(";\n")?;
+                    }
+                    total_bytes += format.keyword(writer, "ALTER TABLE ")?;
+                    total_bytes += writer.write_sql(&self.name)?;
+                    total_bytes += writer.write_sql(" ")?;
+                },
+            }
+            total_bytes += action.write_pg(writer, self.quote_identifiers, format)?;
+            previous = Some(action);
+        }
+
+        Ok(total_bytes)
+    }
+}
+
+impl IntoSql<Pg> for AlterTable {
+    fn into_sql_fmt(&self, writer: &mut dyn fmt::Write) -> Result<usize, Error> {
+        self.into_sql_with_options(writer, &FormatOptions::default())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::table::fields::{CommonFieldOptions, CommonTableOptions};
+
+    #[test]
+    fn add_nullable_column() {
+        let alter = AlterTable::new("posts")
+            .add_column(TableField {
+                options: CommonFieldOptions {
+                    name: "subtitle".to_string(),
+                    primary_key: false,
+                    unique: false,
+                    null: Some(true),
+                    ..Default::default()
+                },
+                kind: Field::Text
+            });
+        let (observed, _) = IntoSql::<Pg>::into_sql_str(&alter).unwrap();
+        assert_eq!(observed, "ALTER TABLE posts ADD COLUMN subtitle TEXT NULL");
+    }
+
+    #[test]
+    fn add_multiple_columns() {
+        let alter = AlterTable::new("posts")
+            .add_column(TableField {
+                options: CommonFieldOptions {
+                    name: "views".to_string(),
+                    primary_key: false,
+                    unique: false,
+                    null: Some(false),
+                    ..Default::default()
+                },
+                kind: Field::Integer
+            })
+            .add_column(TableField {
+                options: CommonFieldOptions {
+                    name: "slug".to_string(),
+                    primary_key: false,
+                    unique: true,
+                    null: None,
+                    ..Default::default()
+                },
+                kind: Field::VarChar { max_length: Some(64) }
+            });
+        let (observed, _) = IntoSql::<Pg>::into_sql_str(&alter).unwrap();
+        assert_eq!(observed, "ALTER TABLE posts ADD COLUMN views INTEGER NOT NULL, ADD COLUMN slug VARCHAR(64) UNIQUE");
+    }
+
+    #[test]
+    fn add_column_if_not_exists() {
+        let alter = AlterTable::new("posts").add_column_if_not_exists(TableField::new("views", Field::Integer));
+        let (observed, _) = IntoSql::<Pg>::into_sql_str(&alter).unwrap();
+        assert_eq!(observed, "ALTER TABLE posts ADD COLUMN IF NOT EXISTS views INTEGER");
+    }
+
+    #[test]
+    fn drop_column() {
+        let alter = AlterTable::new("posts").drop_column("legacy", false).drop_column("author_id", true);
+        let (observed, _) = IntoSql::<Pg>::into_sql_str(&alter).unwrap();
+        assert_eq!(observed, "ALTER TABLE posts DROP COLUMN legacy, DROP COLUMN author_id CASCADE");
+    }
+
+    #[test]
+    fn rename_column_is_its_own_statement() {
+        let alter = AlterTable::new("posts")
+            .drop_column("legacy", false)
+            .rename_column("body", "content")
+            .rename_column("title", "headline");
+        let (observed, _) = IntoSql::<Pg>::into_sql_str(&alter).unwrap();
+        assert_eq!(
+            observed,
+            "ALTER TABLE posts DROP COLUMN legacy;\nALTER TABLE posts RENAME COLUMN body TO content;\nALTER TABLE posts RENAME COLUMN title TO headline"
+        );
+    }
+
+    #[test]
+    fn nullability() {
+        let (observed, _) = IntoSql::<Pg>::into_sql_str(&AlterTable::new("posts").set_not_null("title")).unwrap();
+        assert_eq!(observed, "ALTER TABLE posts ALTER COLUMN title SET NOT NULL");
+
+        let (observed, _) = IntoSql::<Pg>::into_sql_str(&AlterTable::new("posts").drop_not_null("title")).unwrap();
+        assert_eq!(observed, "ALTER TABLE posts ALTER COLUMN title DROP NOT NULL");
+    }
+
+    #[test]
+    fn defaults() {
+        let (observed, _) = IntoSql::<Pg>::into_sql_str(&AlterTable::new("posts").set_default("views", "0")).unwrap();
+        assert_eq!(observed, "ALTER TABLE posts ALTER COLUMN views SET DEFAULT 0");
+
+        let (observed, _) = IntoSql::<Pg>::into_sql_str(&AlterTable::new("posts").drop_default("views")).unwrap();
+        assert_eq!(observed, "ALTER TABLE posts ALTER COLUMN views DROP DEFAULT");
+    }
+
+    #[test]
+    fn column_changes_share_a_statement() {
+        let alter = AlterTable::new("posts")
+            .set_default("published", "false")
+            .set_not_null("published")
+            .drop_default("views");
+        let (observed, _) = IntoSql::<Pg>::into_sql_str(&alter).unwrap();
+        assert_eq!(
+            observed,
+            "ALTER TABLE posts ALTER COLUMN published SET DEFAULT false, ALTER COLUMN published SET NOT NULL, ALTER COLUMN views DROP DEFAULT"
+        );
+    }
+
+    #[test]
+    fn nothing_to_alter() {
+        let alter = AlterTable::new("posts");
+        assert!(matches!(IntoSql::<Pg>::into_sql_str(&alter), Err(Error::InvalidSchema(_))));
+    }
+
+    fn posts(fields: Vec<(&str, Field)>) -> TableDefn {
+        TableDefn {
+            options: CommonTableOptions { name: "posts".to_string(), ..Default::default() },
+            fields: fields.into_iter().map(|(name, kind)| TableField {
+                options: CommonFieldOptions { name: name.to_string(), ..Default::default() },
+                kind
+            }).collect(),
+        }
+    }
+
+    fn render(actions: Vec<AlterAction>) -> String {
+        let alter = AlterTable { actions, ..AlterTable::new("posts") };
+        IntoSql::<Pg>::into_sql_str(&alter).unwrap().0
+    }
+
+    #[test]
+    fn diff_added_column() {
+        let before = posts(vec![("id", Field::Serial)]);
+        let after = posts(vec![("id", Field::Serial), ("body", Field::Text)]);
+        assert_eq!(render(before.diff(&after)), "ALTER TABLE posts ADD COLUMN body TEXT");
+    }
+
+    #[test]
+    fn diff_dropped_column() {
+        let before = posts(vec![("id", Field::Serial), ("body", Field::Text)]);
+        let after = posts(vec![("id", Field::Serial)]);
+        assert_eq!(render(before.diff(&after)), "ALTER TABLE posts DROP COLUMN body");
+    }
+
+    #[test]
+    fn diff_changed_type() {
+        let before = posts(vec![("id", Field::Serial), ("title", Field::VarChar { max_length: Some(80) })]);
+        let after = posts(vec![("id", Field::Serial), ("title", Field::Text)]);
+        assert_eq!(render(before.diff(&after)), "ALTER TABLE posts ALTER COLUMN title TYPE TEXT");
+    }
+
+    #[test]
+    fn diff_of_a_qualified_quoted_table() {
+        let mut before = posts(vec![("id", Field::Serial), ("user", Field::Integer)]);
+        before.options.schema = Some("Blog".to_string());
+        before.options.quote_identifiers = true;
+        let mut after = posts(vec![("id", Field::Serial), ("order", Field::Integer)]);
+        after.options.schema = Some("Blog".to_string());
+        after.options.quote_identifiers = true;
+
+        let alter = AlterTable { actions: before.diff(&after), ..before.alter_table() };
+        let (observed, _) = IntoSql::<Pg>::into_sql_str(&alter).unwrap();
+        assert_eq!(observed, "ALTER TABLE \"Blog\".posts ADD COLUMN \"order\" INTEGER, DROP COLUMN \"user\"");
+
+        let alter = before.alter_table().set_not_null("user").set_default("user", "0").rename_column("user", "order");
+        let (observed, _) = IntoSql::<Pg>::into_sql_str(&alter).unwrap();
+        assert_eq!(
+            observed,
+            "ALTER TABLE \"Blog\".posts ALTER COLUMN \"user\" SET NOT NULL, ALTER COLUMN \"user\" SET DEFAULT 0;\nALTER TABLE \"Blog\".posts RENAME COLUMN \"user\" TO \"order\""
+        );
+    }
+
+    #[test]
+    fn diff_identical_tables() {
+        let before = posts(vec![("id", Field::Serial)]);
+        assert!(before.diff(&posts(vec![("id", Field::Serial)])).is_empty());
+    }
+}
@@ -0,0 +1,345 @@
+use crate::table::constraints::ForeignKeyRef;
+use crate::table::partition::PartitionSpec;
+use crate::table::quote;
+use crate::table::fields::{
+    CommonFieldOptions,
+    CommonTableOptions,
+    Field,
+    GeneratedColumn,
+    IdentityKind,
+    TableDefn,
+    TableField,
+    TableKind,
+};
+
+
+/// Fluent construction of a [`TableDefn`], see [`TableDefn::builder`].
+#[derive(Debug)]
+pub struct TableDefnBuilder {
+    options: CommonTableOptions,
+    fields: Vec<TableField>,
+}
+
+impl TableDefn {
+    /// Start building a table called `name`.
+    ///
+    /// ```
+    /// use ross_db::table::fields::{Field, TableDefn};
+    ///
+    /// let posts = TableDefn::builder("posts")
+    ///     .if_not_exists()
+    ///     .column("id", Field::Serial, |c| c.primary_key())
+    ///     .column("title", Field::Text, |c| c.not_null())
+    ///     .build();
+    /// assert_eq!(posts.fields.len(), 2);
+    /// ```
+    pub fn builder(name: impl Into<String>) -> TableDefnBuilder {
+        TableDefnBuilder {
+            options: CommonTableOptions::new(name),
+            fields: Vec::new(),
+        }
+    }
+}
+
+impl TableDefnBuilder {
+    pub fn if_not_exists(mut self) -> Self {
+        self.options.if_not_exists = true;
+        self
+    }
+
+    pub fn schema(mut self, schema: impl Into<String>) -> Self {
+        self.options.schema = Some(schema.into());
+        self
+    }
+
+    pub fn kind(mut self, kind: TableKind) -> Self {
+        self.options.kind = Some(kind);
+        self
+    }
+
+    pub fn unlogged(mut self) -> Self {
+        self.options.unlogged = true;
+        self
+    }
+
+    pub fn quote_identifiers(mut self) -> Self {
+        self.options.quote_identifiers = true;
+        self
+    }
+
+    /// Declare a table-level `PRIMARY KEY (...)` over the given columns.
+    pub fn primary_key<I, S>(mut self, columns: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.options.primary_key = columns.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Add a table-level `UNIQUE (...)` over the given columns, optionally named.
+    pub fn unique<I, S>(mut self, name: Option<&str>, columns: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.options.unique_constraints.push((name.map(String::from), columns.into_iter().map(Into::into).collect()));
+        self
+    }
+
+    /// Add a table-level `CHECK`, optionally named.
+    pub fn check(mut self, name: Option<&str>, expression: impl Into<String>) -> Self {
+        self.options.checks.push((name.map(String::from), expression.into()));
+        self
+    }
+
+    pub fn comment(mut self, comment: impl Into<String>) -> Self {
+        self.options.comment = Some(comment.into());
+        self
+    }
+
+    pub fn partition_by(mut self, partition_by: PartitionSpec) -> Self {
+        self.options.partition_by = Some(partition_by);
+        self
+    }
+
+    pub fn storage_param(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.options.storage_params.push((key.into(), value.into()));
+        self
+    }
+
+    pub fn tablespace(mut self, tablespace: impl Into<String>) -> Self {
+        self.options.tablespace = Some(tablespace.into());
+        self
+    }
+
+    pub fn row_level_security(mut self) -> Self {
+        self.options.row_level_security = true;
+        self
+    }
+
+    pub fn force_rls(mut self) -> Self {
+        self.options.force_rls = true;
+        self
+    }
+
+    pub fn owner(mut self, owner: impl Into<String>) -> Self {
+        self.options.owner = Some(owner.into());
+        self
+    }
+
+    pub fn inherits(mut self, parent: impl Into<String>) -> Self {
+        self.options.inherits.push(parent.into());
+        self
+    }
+
+    /// Append a column; `configure` adjusts its options before it is added.
+    pub fn column<F>(mut self, name: impl Into<String>, kind: Field, configure: F) -> Self
+    where
+        F: FnOnce(&mut CommonFieldOptions) -> &mut CommonFieldOptions,
+    {
+        let mut options = CommonFieldOptions::new(name);
+        configure(&mut options);
+        self.fields.push(TableField { options, kind });
+        self
+    }
+
+    /// Append a `TEXT` column capped at `max` characters by a generated
+    /// `CHECK (char_length(name) <= max)`; `configure` runs afterwards and may replace the check.
+    pub fn bounded_text<F>(self, name: impl Into<String>, max: usize, configure: F) -> Self
+    where
+        F: FnOnce(&mut CommonFieldOptions) -> &mut CommonFieldOptions,
+    {
+        let name = name.into();
+        let check = format!("char_length({}) <= {}", quote::identifier(&name, self.options.quote_identifiers), max);
+        self.column(name, Field::Text, |c| configure(c.check(check)))
+    }
+
+    pub fn build(self) -> TableDefn {
+        TableDefn {
+            fields: self.fields,
+            options: self.options,
+        }
+    }
+}
+
+impl CommonFieldOptions {
+    pub fn primary_key(&mut self) -> &mut Self {
+        self.primary_key = true;
+        self
+    }
+
+    pub fn unique(&mut self) -> &mut Self {
+        self.unique = true;
+        self
+    }
+
+    /// Name the column's `UNIQUE` constraint, implying `unique()`.
+    pub fn unique_named(&mut self, name: impl Into<String>) -> &mut Self {
+        self.unique = true;
+        self.unique_constraint = Some(name.into());
+        self
+    }
+
+    /// Mark the column explicitly `NULL`.
+    pub fn nullable(&mut self) -> &mut Self {
+        self.null = Some(true);
+        self
+    }
+
+    pub fn not_null(&mut self) -> &mut Self {
+        self.null = Some(false);
+        self
+    }
+
+    pub fn default_value(&mut self, expression: impl Into<String>) -> &mut Self {
+        self.default = Some(expression.into());
+        self
+    }
+
+    pub fn check(&mut self, expression: impl Into<String>) -> &mut Self {
+        self.check = Some(expression.into());
+        self
+    }
+
+    /// Restrict the column to `values` with `CHECK (name IN ('a', 'b', ...))`, quoting each value.
+    /// The check sits next to any `check()`; an empty `values` is rejected when the table is rendered.
+    pub fn check_in(&mut self, values: &[&str]) -> &mut Self {
+        self.allowed_values = Some(values.iter().map(|value| value.to_string()).collect());
+        self
+    }
+
+    pub fn collation(&mut self, collation: impl Into<String>) -> &mut Self {
+        self.collation = Some(collation.into());
+        self
+    }
+
+    pub fn references(&mut self, table: impl Into<String>, column: impl Into<String>) -> &mut Self {
+        self.references = Some(ForeignKeyRef {
+            table: table.into(),
+            column: column.into(),
+            ..Default::default()
+        });
+        self
+    }
+
+    pub fn identity(&mut self, identity: IdentityKind) -> &mut Self {
+        self.identity = Some(identity);
+        self
+    }
+
+    pub fn generated(&mut self, expression: impl Into<String>, stored: bool) -> &mut Self {
+        self.generated = Some(GeneratedColumn {
+            expression: expression.into(),
+            stored,
+        });
+        self
+    }
+
+    pub fn comment(&mut self, comment: impl Into<String>) -> &mut Self {
+        self.comment = Some(comment.into());
+        self
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use diesel::pg::Pg;
+
+    use super::*;
+    use crate::Error;
+    use crate::table::fields::IntoSql;
+
+    #[test]
+    fn builder_matches_struct_literal() {
+        let posts = TableDefn::builder("posts")
+            .if_not_exists()
+            .kind(TableKind::Global)
+            .column("id", Field::Serial, |c| c.primary_key())
+            .column("title", Field::Char { max_length: Some(10) }, |c| c.not_null())
+            .column("body", Field::Text, |c| c.not_null())
+            .column("published", Field::Boolean, |c| c.not_null())
+            .build();
+        let (observed, _) = IntoSql::<Pg>::into_sql_str(&posts).unwrap();
+        let expected = "CREATE GLOBAL TEMPORARY TABLE IF NOT EXISTS posts (\n\tid SERIAL PRIMARY KEY,\n\ttitle CHAR(10) NOT NULL,\n\tbody TEXT NOT NULL,\n\tpublished BOOLEAN NOT NULL\n)";
+        assert_eq!(observed, expected);
+    }
+
+    #[test]
+    fn columns_keep_insertion_order() {
+        let posts = TableDefn::builder("posts")
+            .column("title", Field::Text, |c| c)
+            .column("id", Field::Serial, |c| c.primary_key())
+            .column("body", Field::Text, |c| c)
+            .build();
+        let names: Vec<&str> = posts.fields.iter().map(|field| field.options.name.as_str()).collect();
+        assert_eq!(names, ["title", "id", "body"]);
+        let (observed, _) = IntoSql::<Pg>::into_sql_str(&posts).unwrap();
+        assert_eq!(observed, "CREATE TABLE posts (\n\ttitle TEXT,\n\tid SERIAL PRIMARY KEY,\n\tbody TEXT\n)");
+    }
+
+    #[test]
+    fn sort_columns_by_name() {
+        let mut posts = TableDefn::builder("posts")
+            .column("title", Field::Text, |c| c)
+            .column("id", Field::Serial, |c| c.primary_key())
+            .column("body", Field::Text, |c| c)
+            .build();
+        posts.sort_columns_by_name();
+        let (observed, _) = IntoSql::<Pg>::into_sql_str(&posts).unwrap();
+        assert_eq!(observed, "CREATE TABLE posts (\n\tbody TEXT,\n\tid SERIAL PRIMARY KEY,\n\ttitle TEXT\n)");
+    }
+
+    #[test]
+    fn bounded_text_adds_a_length_check() {
+        let posts = TableDefn::builder("posts")
+            .bounded_text("title", 120, |c| c.not_null())
+            .build();
+        assert!(matches!(posts.fields[0].kind, Field::Text));
+        let (observed, _) = IntoSql::<Pg>::into_sql_str(&posts).unwrap();
+        let expected = "CREATE TABLE posts (\n\ttitle TEXT NOT NULL CHECK (char_length(title) <= 120)\n)";
+        assert_eq!(observed, expected);
+    }
+
+    #[test]
+    fn check_in_quotes_values() {
+        let accounts = TableDefn::builder("accounts")
+            .column("status", Field::Text, |c| c.not_null().check_in(&["active", "inactive", "won't renew"]))
+            .build();
+        let (observed, _) = IntoSql::<Pg>::into_sql_str(&accounts).unwrap();
+        let expected = "CREATE TABLE accounts (\n\tstatus TEXT NOT NULL CHECK (status IN ('active', 'inactive', 'won''t renew'))\n)";
+        assert_eq!(observed, expected);
+    }
+
+    #[test]
+    fn check_in_quotes_the_column() {
+        let orders = TableDefn::builder("orders")
+            .quote_identifiers()
+            .column("order", Field::Text, |c| c.check("length(\"order\") < 10").check_in(&["first", "second"]))
+            .build();
+        let (observed, _) = IntoSql::<Pg>::into_sql_str(&orders).unwrap();
+        let expected = "CREATE TABLE orders (\n\t\"order\" TEXT CHECK (length(\"order\") < 10) CHECK (\"order\" IN ('first', 'second'))\n)";
+        assert_eq!(observed, expected);
+    }
+
+    #[test]
+    fn check_in_without_values() {
+        let accounts = TableDefn::builder("accounts")
+            .column("status", Field::Text, |c| c.check_in(&[]))
+            .build();
+        assert!(matches!(IntoSql::<Pg>::into_sql_str(&accounts), Err(Error::InvalidSchema(_))));
+    }
+
+    #[test]
+    fn builder_column_options() {
+        let posts = TableDefn::builder("posts")
+            .column("id", Field::Uuid, |c| c.primary_key().default_value("gen_random_uuid()"))
+            .column("author_id", Field::Integer, |c| c.not_null().references("users", "id"))
+            .column("slug", Field::Text, |c| c.unique().collation("C"))
+            .build();
+        let (observed, _) = IntoSql::<Pg>::into_sql_str(&posts).unwrap();
+        let expected = "CREATE TABLE posts (\n\tid UUID DEFAULT gen_random_uuid() PRIMARY KEY,\n\tauthor_id INTEGER NOT NULL REFERENCES users(id),\n\tslug TEXT COLLATE \"C\" UNIQUE\n)";
+        assert_eq!(observed, expected);
+    }
+}
@@ -0,0 +1,455 @@
+use std::fmt;
+
+use diesel::pg::Pg;
+
+use crate::Error;
+use crate::table::fields::{IntoSql, SqlWrite};
+use crate::table::format::FormatOptions;
+use crate::table::index::IndexMethod;
+use crate::table::quote;
+
+
+/// What happens to referencing rows when the referenced row changes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ReferentialAction {
+    Cascade,
+    Restrict,
+    SetNull,
+    SetDefault,
+    NoAction,
+}
+
+impl IntoSql<Pg> for ReferentialAction {
+    fn into_sql_fmt(&self, writer: &mut dyn fmt::Write) -> Result<usize, Error> {
+        let action = match self {
+            Self::Cascade => "CASCADE",
+            Self::Restrict => "RESTRICT",
+            Self::SetNull => "SET NULL",
+            Self::SetDefault => "SET DEFAULT",
+            Self::NoAction => "NO ACTION",
+        };
+        writer.write_sql(action)
+    }
+}
+
+
+/// When a deferrable constraint is checked, `DEFERRABLE INITIALLY DEFERRED` and friends.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Deferrable {
+    pub deferrable: bool,
+    pub initially_deferred: bool,
+}
+
+impl IntoSql<Pg> for Deferrable {
+    fn into_sql_fmt(&self, writer: &mut dyn fmt::Write) -> Result<usize, Error> {
+        match (self.deferrable, self.initially_deferred) {
+            (true, true) => writer.write_sql("DEFERRABLE INITIALLY DEFERRED"),
+            (true, false) => writer.write_sql("DEFERRABLE INITIALLY IMMEDIATE"),
+            (false, false) => writer.write_sql("NOT DEFERRABLE"),
+            (false, true) => Err(Error::InvalidSchema("a constraint that is not deferrable cannot be initially deferred".to_string())),
+        }
+    }
+}
+
+
+/// The target of an inline `REFERENCES` column constraint.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct ForeignKeyRef {
+    /// The schema of the referenced table, rendered as `schema.table` when set.
+    pub schema: Option<String>,
+    pub table: String,
+    pub column: String,
+    pub on_delete: Option<ReferentialAction>,
+    pub on_update: Option<ReferentialAction>,
+    pub deferrable: Option<Deferrable>,
+}
+
+fn qualified(schema: Option<&str>, table: &str, quote: &impl Fn(&str) -> String) -> String {
+    match schema {
+        Some(schema) => format!("{}.{}", quote(schema), quote(table)),
+        None => quote(table),
+    }
+}
+
+fn column_list(columns: &[String], quote: &impl Fn(&str) -> String) -> String {
+    columns.iter().map(|column| quote(column)).collect::<Vec<_>>().join(", ")
+}
+
+impl ForeignKeyRef {
+    pub(crate) fn write_pg(&self, writer: &mut dyn fmt::Write, quote_identifiers: bool, format: &FormatOptions) -> Result<usize, Error> {
+        self.write_with(writer, format, |name| quote::identifier(name, quote_identifiers).into_owned())
+    }
+
+    pub(crate) fn write_with(&self, writer: &mut dyn fmt::Write, format: &FormatOptions, quote: impl Fn(&str) -> String) -> Result<usize, Error> {
+        let mut total_bytes = 0;
+        total_bytes += format.keyword(writer, "REFERENCES ")?;
+        total_bytes += writer.write_sql(&qualified(self.schema.as_deref(), &self.table, &quote))?;
+        total_bytes += writer.write_sql("(")?;
+        total_bytes += writer.write_sql(&quote(&self.column))?;
+        total_bytes += writer.write_sql(")")?;
+        if let Some(on_delete) = &self.on_delete {
+            total_bytes += format.keyword(writer, " ON DELETE ")?;
+            total_bytes += format.keywords(writer, on_delete)?;
+        }
+        if let Some(on_update) = &self.on_update {
+            total_bytes += format.keyword(writer, " ON UPDATE ")?;
+            total_bytes += format.keywords(writer, on_update)?;
+        }
+        if let Some(deferrable) = &self.deferrable {
+            total_bytes += writer.write_sql(" ")?;
+            total_bytes += format.keywords(writer, deferrable)?;
+        }
+        Ok(total_bytes)
+    }
+}
+
+impl IntoSql<Pg> for ForeignKeyRef {
+    fn into_sql_fmt(&self, writer: &mut dyn fmt::Write) -> Result<usize, Error> {
+        self.write_pg(writer, false, &FormatOptions::default())
+    }
+}
+
+
+
+/// A table-level `FOREIGN KEY (...) REFERENCES table (...)` constraint, for composite keys.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct TableForeignKey {
+    pub name: Option<String>,
+    pub columns: Vec<String>,
+    /// The schema of the referenced table, rendered as `schema.table` when set.
+    pub schema: Option<String>,
+    pub table: String,
+    pub referenced_columns: Vec<String>,
+    pub on_delete: Option<ReferentialAction>,
+    pub on_update: Option<ReferentialAction>,
+    pub deferrable: Option<Deferrable>,
+}
+
+impl TableForeignKey {
+    pub(crate) fn write_pg(&self, writer: &mut dyn fmt::Write, quote_identifiers: bool, format: &FormatOptions) -> Result<usize, Error> {
+        self.write_with(writer, format, |name| quote::identifier(name, quote_identifiers).into_owned())
+    }
+
+    pub(crate) fn write_with(&self, writer: &mut dyn fmt::Write, format: &FormatOptions, quote: impl Fn(&str) -> String) -> Result<usize, Error> {
+        if self.columns.is_empty() || self.columns.len() != self.referenced_columns.len() {
+            return Err(Error::InvalidSchema(format!(
+                "foreign key to {} must reference as many columns as it covers",
+                self.table
+            )));
+        }
+
+        let mut total_bytes = 0;
+        if let Some(name) = &self.name {
+            total_bytes += format.keyword(writer, "CONSTRAINT ")?;
+            total_bytes += writer.write_sql(&quote(name))?;
+            total_bytes += writer.write_sql(" ")?;
+        }
+        total_bytes += format.keyword(writer, "FOREIGN KEY (")?;
+        total_bytes += writer.write_sql(&column_list(&self.columns, &quote))?;
+        total_bytes += format.keyword(writer, ") REFERENCES ")?;
+        total_bytes += writer.write_sql(&qualified(self.schema.as_deref(), &self.table, &quote))?;
+        total_bytes += writer.write_sql(" (")?;
+        total_bytes += writer.write_sql(&column_list(&self.referenced_columns, &quote))?;
+        total_bytes += writer.write_sql(")")?;
+        if let Some(on_delete) = &self.on_delete {
+            total_bytes += format.keyword(writer, " ON DELETE ")?;
+            total_bytes += format.keywords(writer, on_delete)?;
+        }
+        if let Some(on_update) = &self.on_update {
+            total_bytes += format.keyword(writer, " ON UPDATE ")?;
+            total_bytes += format.keywords(writer, on_update)?;
+        }
+        if let Some(deferrable) = &self.deferrable {
+            total_bytes += writer.write_sql(" ")?;
+            total_bytes += format.keywords(writer, deferrable)?;
+        }
+        Ok(total_bytes)
+    }
+}
+
+impl IntoSql<Pg> for TableForeignKey {
+    fn into_sql_fmt(&self, writer: &mut dyn fmt::Write) -> Result<usize, Error> {
+        self.write_pg(writer, false, &FormatOptions::default())
+    }
+}
+
+
+
+/// An `EXCLUDE USING method (element WITH operator, ...)` table constraint.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ExcludeConstraint {
+    pub name: Option<String>,
+    pub method: IndexMethod,
+    /// `(element, operator)` pairs, both written verbatim.
+    pub elements: Vec<(String, String)>,
+}
+
+impl ExcludeConstraint {
+    pub(crate) fn write_pg(&self, writer: &mut dyn fmt::Write, format: &FormatOptions) -> Result<usize, Error> {
+        if self.elements.is_empty() {
+            return Err(Error::InvalidSchema("EXCLUDE needs at least one element".to_string()));
+        }
+
+        let mut total_bytes = 0;
+        if let Some(name) = &self.name {
+            total_bytes += format.keyword(writer, "CONSTRAINT ")?;
+            total_bytes += writer.write_sql(name)?;
+            total_bytes += writer.write_sql(" ")?;
+        }
+        total_bytes += format.keyword(writer, "EXCLUDE USING ")?;
+        total_bytes += self.method.into_sql_fmt(writer)?;
+        total_bytes += writer.write_sql(" (")?;
+        for (index, (element, operator)) in self.elements.iter().enumerate() {
+            if index > 0 {
+                total_bytes += writer.write_sql(", ")?;
+            }
+            total_bytes += writer.write_sql(element)?;
+            total_bytes += format.keyword(writer, " WITH ")?;
+            total_bytes += writer.write_sql(operator)?;
+        }
+        total_bytes += writer.write_sql(")")?;
+        Ok(total_bytes)
+    }
+}
+
+impl IntoSql<Pg> for ExcludeConstraint {
+    fn into_sql_fmt(&self, writer: &mut dyn fmt::Write) -> Result<usize, Error> {
+        self.write_pg(writer, &FormatOptions::default())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn foreign_key_ref() {
+        let users = ForeignKeyRef { table: "users".to_string(), column: "id".to_string(), ..Default::default() };
+        let (observed, _) = IntoSql::<Pg>::into_sql_str(&users).unwrap();
+        assert_eq!(observed, "REFERENCES users(id)");
+    }
+
+    #[test]
+    fn column_references() {
+        use crate::table::fields::{CommonFieldOptions, CommonTableOptions, Field, TableDefn, TableField};
+
+        let posts = TableDefn {
+            options: CommonTableOptions { name: "posts".to_string(), ..Default::default() },
+            fields: vec![
+                TableField {
+                    options: CommonFieldOptions {
+                        name: "id".to_string(),
+                        primary_key: true,
+                        ..Default::default()
+                    },
+                    kind: Field::Serial
+                },
+                TableField {
+                    options: CommonFieldOptions {
+                        name: "author_id".to_string(),
+                        null: Some(false),
+                        references: Some(ForeignKeyRef { table: "users".to_string(), column: "id".to_string(), ..Default::default() }),
+                        ..Default::default()
+                    },
+                    kind: Field::Integer
+                },
+            ],
+        };
+        let (observed, _) = IntoSql::<Pg>::into_sql_str(&posts).unwrap();
+        let expected = "CREATE TABLE posts (\n\tid SERIAL PRIMARY KEY,\n\tauthor_id INTEGER NOT NULL REFERENCES users(id)\n)";
+        assert_eq!(observed, expected);
+    }
+
+    #[test]
+    fn quoted_column_references() {
+        use crate::table::fields::{Field, TableDefn};
+
+        let mut orders = TableDefn::builder("orders")
+            .quote_identifiers()
+            .column("user", Field::Integer, |c| c.references("User.v2", "order"))
+            .build();
+        let (observed, _) = IntoSql::<Pg>::into_sql_str(&orders).unwrap();
+        assert_eq!(observed, "CREATE TABLE orders (\n\t\"user\" INTEGER REFERENCES \"User.v2\"(\"order\")\n)");
+
+        orders.fields[0].options.references.as_mut().unwrap().schema = Some("auth".to_string());
+        let (observed, _) = IntoSql::<Pg>::into_sql_str(&orders).unwrap();
+        assert_eq!(observed, "CREATE TABLE orders (\n\t\"user\" INTEGER REFERENCES auth.\"User.v2\"(\"order\")\n)");
+    }
+
+    #[test]
+    fn referential_actions() {
+        let cases = [
+            (ReferentialAction::Cascade, "CASCADE"),
+            (ReferentialAction::Restrict, "RESTRICT"),
+            (ReferentialAction::SetNull, "SET NULL"),
+            (ReferentialAction::SetDefault, "SET DEFAULT"),
+            (ReferentialAction::NoAction, "NO ACTION"),
+        ];
+        for (action, expected) in cases {
+            let (observed, _) = IntoSql::<Pg>::into_sql_str(&action).unwrap();
+            assert_eq!(observed, expected);
+        }
+    }
+
+    #[test]
+    fn foreign_key_ref_with_actions() {
+        let users = ForeignKeyRef {
+            schema: None,
+            table: "users".to_string(),
+            column: "id".to_string(),
+            on_delete: Some(ReferentialAction::Cascade),
+            on_update: Some(ReferentialAction::Restrict),
+            deferrable: None,
+        };
+        let (observed, _) = IntoSql::<Pg>::into_sql_str(&users).unwrap();
+        assert_eq!(observed, "REFERENCES users(id) ON DELETE CASCADE ON UPDATE RESTRICT");
+
+        let users = ForeignKeyRef {
+            table: "users".to_string(),
+            column: "id".to_string(),
+            on_update: Some(ReferentialAction::SetNull),
+            ..Default::default()
+        };
+        let (observed, _) = IntoSql::<Pg>::into_sql_str(&users).unwrap();
+        assert_eq!(observed, "REFERENCES users(id) ON UPDATE SET NULL");
+    }
+
+    #[test]
+    fn composite_foreign_key() {
+        use crate::table::fields::{CommonFieldOptions, CommonTableOptions, Field, TableDefn, TableField};
+
+        let line_items = TableDefn {
+            options: CommonTableOptions {
+                name: "line_items".to_string(),
+                foreign_keys: vec![TableForeignKey {
+                    columns: vec!["order_id".to_string(), "order_region".to_string()],
+                    table: "orders".to_string(),
+                    referenced_columns: vec!["id".to_string(), "region".to_string()],
+                    on_delete: Some(ReferentialAction::Cascade),
+                    ..Default::default()
+                }],
+                ..Default::default()
+            },
+            fields: vec![
+                TableField {
+                    options: CommonFieldOptions { name: "order_id".to_string(), ..Default::default() },
+                    kind: Field::Integer
+                },
+                TableField {
+                    options: CommonFieldOptions { name: "order_region".to_string(), ..Default::default() },
+                    kind: Field::Text
+                },
+            ],
+        };
+        let (observed, _) = IntoSql::<Pg>::into_sql_str(&line_items).unwrap();
+        let expected = "CREATE TABLE line_items (\n\torder_id INTEGER,\n\torder_region TEXT,\n\tFOREIGN KEY (order_id, order_region) REFERENCES orders (id, region) ON DELETE CASCADE\n)";
+        assert_eq!(observed, expected);
+    }
+
+    #[test]
+    fn quoted_composite_foreign_key() {
+        use crate::table::fields::{Field, TableDefn};
+
+        let mut line_items = TableDefn::builder("line_items")
+            .quote_identifiers()
+            .column("order", Field::Integer, |c| c)
+            .column("user", Field::Integer, |c| c)
+            .build();
+        line_items.options.foreign_keys.push(TableForeignKey {
+            columns: vec!["order".to_string(), "user".to_string()],
+            table: "Orders".to_string(),
+            referenced_columns: vec!["id".to_string(), "user".to_string()],
+            ..Default::default()
+        });
+        let (observed, _) = IntoSql::<Pg>::into_sql_str(&line_items).unwrap();
+        let expected = "CREATE TABLE line_items (\n\t\"order\" INTEGER,\n\t\"user\" INTEGER,\n\tFOREIGN KEY (\"order\", \"user\") REFERENCES \"Orders\" (id, \"user\")\n)";
+        assert_eq!(observed, expected);
+    }
+
+    #[test]
+    fn foreign_key_column_count_mismatch() {
+        let foreign_key = TableForeignKey {
+            columns: vec!["order_id".to_string(), "order_region".to_string()],
+            table: "orders".to_string(),
+            referenced_columns: vec!["id".to_string()],
+            ..Default::default()
+        };
+        assert!(matches!(IntoSql::<Pg>::into_sql_str(&foreign_key), Err(Error::InvalidSchema(_))));
+    }
+
+    #[test]
+    fn deferrable_timing() {
+        let cases = [
+            (true, true, Some("DEFERRABLE INITIALLY DEFERRED")),
+            (true, false, Some("DEFERRABLE INITIALLY IMMEDIATE")),
+            (false, false, Some("NOT DEFERRABLE")),
+            (false, true, None),
+        ];
+        for (deferrable, initially_deferred, expected) in cases {
+            let timing = Deferrable { deferrable, initially_deferred };
+            match expected {
+                Some(expected) => assert_eq!(IntoSql::<Pg>::into_sql_str(&timing).unwrap().0, expected),
+                None => assert!(matches!(IntoSql::<Pg>::into_sql_str(&timing), Err(Error::InvalidSchema(_)))),
+            }
+        }
+    }
+
+    #[test]
+    fn deferrable_foreign_keys() {
+        let users = ForeignKeyRef {
+            table: "users".to_string(),
+            column: "id".to_string(),
+            on_delete: Some(ReferentialAction::Cascade),
+            deferrable: Some(Deferrable { deferrable: true, initially_deferred: true }),
+            ..Default::default()
+        };
+        let (observed, _) = IntoSql::<Pg>::into_sql_str(&users).unwrap();
+        assert_eq!(observed, "REFERENCES users(id) ON DELETE CASCADE DEFERRABLE INITIALLY DEFERRED");
+
+        let orders = TableForeignKey {
+            columns: vec!["order_id".to_string()],
+            table: "orders".to_string(),
+            referenced_columns: vec!["id".to_string()],
+            deferrable: Some(Deferrable::default()),
+            ..Default::default()
+        };
+        let (observed, _) = IntoSql::<Pg>::into_sql_str(&orders).unwrap();
+        assert_eq!(observed, "FOREIGN KEY (order_id) REFERENCES orders (id) NOT DEFERRABLE");
+    }
+
+    #[test]
+    fn gist_exclusion() {
+        use crate::table::fields::{CommonFieldOptions, CommonTableOptions, Field, TableDefn, TableField};
+
+        let bookings = TableDefn {
+            options: CommonTableOptions {
+                name: "bookings".to_string(),
+                exclusions: vec![ExcludeConstraint {
+                    name: None,
+                    method: IndexMethod::Gist,
+                    elements: vec![
+                        ("room".to_string(), "=".to_string()),
+                        ("during".to_string(), "&&".to_string()),
+                    ],
+                }],
+                ..Default::default()
+            },
+            fields: vec![
+                TableField {
+                    options: CommonFieldOptions { name: "room".to_string(), ..Default::default() },
+                    kind: Field::Integer
+                },
+            ],
+        };
+        let (observed, _) = IntoSql::<Pg>::into_sql_str(&bookings).unwrap();
+        assert_eq!(observed, "CREATE TABLE bookings (\n\troom INTEGER,\n\tEXCLUDE USING gist (room WITH =, during WITH &&)\n)");
+    }
+}
@@ -0,0 +1,90 @@
+use diesel::pg::Pg;
+
+use crate::Error;
+use crate::table::fields::{Field, IntoSql, TableDefn, TableField};
+
+
+/// Plain-data metadata about one column, see [`TableDefn::columns`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ColumnInfo {
+    pub name: String,
+    /// The Postgres type as it appears in `CREATE TABLE`, e.g. `VARCHAR(80)`.
+    pub sql_type: String,
+    pub nullable: bool,
+    /// Also set for columns of a table-level, possibly composite, primary key.
+    pub primary_key: bool,
+    /// Also set for columns covered on their own by a table-level `UNIQUE`.
+    pub unique: bool,
+}
+
+impl TableDefn {
+    /// Describe every column in order, without going through the rendered SQL.
+    pub fn columns(&self) -> Result<Vec<ColumnInfo>, Error> {
+        let primary_key = self.primary_key_columns();
+        self.fields
+            .iter()
+            .map(|field| {
+                let name = field.options.name.as_str();
+                let (sql_type, _) = IntoSql::<Pg>::into_sql_str(&field.kind)?;
+                let unique = field.options.unique
+                    || self.options.unique_constraints.iter().any(|(_, columns)| columns.len() == 1 && columns[0] == name);
+                Ok(ColumnInfo {
+                    name: name.to_string(),
+                    sql_type,
+                    nullable: !self.is_not_null(field, &primary_key),
+                    primary_key: primary_key.contains(&name),
+                    unique,
+                })
+            })
+            .collect()
+    }
+
+    pub(crate) fn primary_key_columns(&self) -> Vec<&str> {
+        if self.options.primary_key.is_empty() {
+            self.fields
+                .iter()
+                .filter(|field| field.options.primary_key)
+                .map(|field| field.options.name.as_str())
+                .collect()
+        } else {
+            self.options.primary_key.iter().map(String::as_str).collect()
+        }
+    }
+
+    pub(crate) fn is_not_null(&self, field: &TableField, primary_key: &[&str]) -> bool {
+        field.options.null == Some(false)
+            || primary_key.contains(&field.options.name.as_str())
+            || field.options.identity.is_some()
+            || matches!(field.kind, Field::SmallSerial | Field::Serial | Field::BigSerial)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn posts_metadata() {
+        let posts = TableDefn::builder("posts")
+            .unique(None, ["slug"])
+            .column("id", Field::Serial, |c| c.primary_key())
+            .column("title", Field::VarChar { max_length: Some(80) }, |c| c.not_null())
+            .column("slug", Field::Text, |c| c.not_null())
+            .column("body", Field::Text, |c| c)
+            .build();
+        let column = |name: &str, sql_type: &str, nullable, primary_key, unique| ColumnInfo {
+            name: name.to_string(),
+            sql_type: sql_type.to_string(),
+            nullable,
+            primary_key,
+            unique,
+        };
+        assert_eq!(posts.columns().unwrap(), vec![
+            column("id", "SERIAL", false, true, false),
+            column("title", "VARCHAR(80)", false, false, false),
+            column("slug", "TEXT", false, false, true),
+            column("body", "TEXT", true, false, false),
+        ]);
+    }
+}
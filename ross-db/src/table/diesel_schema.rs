@@ -0,0 +1,189 @@
+use diesel::sql_types;
+
+use crate::Error;
+use crate::table::fields::{Field, TableDefn};
+
+
+/// A diesel SQL type marker with a matching [`Field`], see [`Field::from_diesel_sql_type`].
+pub trait DieselSqlType {
+    fn field() -> Field;
+}
+
+macro_rules! diesel_sql_types {
+    ($($sql_type:ty => $field:expr,)*) => {
+        $(
+            impl DieselSqlType for $sql_type {
+                fn field() -> Field {
+                    $field
+                }
+            }
+        )*
+    };
+}
+
+diesel_sql_types! {
+    sql_types::Text => Field::Text,
+    sql_types::SmallInt => Field::SmallInt,
+    sql_types::Integer => Field::Integer,
+    sql_types::BigInt => Field::BigInt,
+    sql_types::Float => Field::Real,
+    sql_types::Double => Field::DoublePrecision,
+    sql_types::Bool => Field::Boolean,
+    sql_types::Date => Field::Date,
+    sql_types::Time => Field::Time { with_timezone: false, precision: None },
+    sql_types::Timestamp => Field::Timestamp { with_timezone: false, precision: None },
+    sql_types::Timestamptz => Field::Timestamp { with_timezone: true, precision: None },
+    sql_types::Binary => Field::Bytea,
+    sql_types::Uuid => Field::Uuid,
+    sql_types::Json => Field::Json,
+    sql_types::Jsonb => Field::Jsonb,
+    sql_types::Money => Field::Money,
+    sql_types::Inet => Field::Inet,
+    sql_types::Cidr => Field::Cidr,
+    sql_types::MacAddr => Field::MacAddr,
+    sql_types::Interval => Field::Interval { fields: None, precision: None },
+}
+
+impl<T: DieselSqlType> DieselSqlType for sql_types::Array<T> {
+    fn field() -> Field {
+        Field::Array(Box::new(T::field()))
+    }
+}
+
+impl<T: DieselSqlType + sql_types::SqlType> DieselSqlType for sql_types::Nullable<T> {
+    fn field() -> Field {
+        T::field()
+    }
+}
+
+impl Field {
+    /// The closest `Field` to a diesel SQL type, e.g. `Field::from_diesel_sql_type::<sql_types::Bool>()`.
+    pub fn from_diesel_sql_type<T: DieselSqlType>() -> Field {
+        T::field()
+    }
+}
+
+
+fn sql_type(field: &Field) -> Result<String, Error> {
+    let sql_type = match field {
+        Field::Char { .. } => "Bpchar",
+        Field::VarChar { .. } => "Varchar",
+        Field::Text => "Text",
+        Field::SmallSerial | Field::SmallInt => "SmallInt",
+        Field::Serial | Field::Integer => "Integer",
+        Field::BigSerial | Field::BigInt => "BigInt",
+        Field::Real => "Float",
+        Field::DoublePrecision => "Double",
+        Field::Numeric { .. } => "Numeric",
+        Field::Money => "Money",
+        Field::Boolean => "Bool",
+        Field::Date => "Date",
+        Field::Time { with_timezone: false, .. } => "Time",
+        Field::Timestamp { with_timezone: false, .. } => "Timestamp",
+        Field::Timestamp { with_timezone: true, .. } => "Timestamptz",
+        Field::Uuid => "Uuid",
+        Field::Json => "Json",
+        Field::Jsonb => "Jsonb",
+        Field::Bytea => "Binary",
+        Field::Inet => "Inet",
+        Field::Cidr => "Cidr",
+        Field::MacAddr => "MacAddr",
+        Field::Interval { .. } => "Interval",
+        Field::Array(inner) => return Ok(format!("Array<{}>", sql_type(inner)?)),
+        Field::Time { with_timezone: true, .. } | Field::Bit { .. } | Field::BitVarying { .. } | Field::Enum { .. } => {
+            return Err(Error::InvalidSchema(format!("{:?} columns have no diesel SQL type", field)));
+        },
+    };
+    Ok(sql_type.to_string())
+}
+
+
+impl TableDefn {
+    /// Render a diesel `table!` block describing this table, for use with diesel's query DSL.
+    ///
+    /// Columns are `Nullable` unless they are `NOT NULL`, part of the primary key,
+    /// serial, or identity columns, mirroring what Postgres itself enforces.
+    pub fn to_diesel_schema(&self) -> Result<String, Error> {
+        let primary_key = self.primary_key_columns();
+
+        let mut schema = String::from("table! {\n    ");
+        if let Some(name) = &self.options.schema {
+            schema.push_str(name);
+            schema.push('.');
+        }
+        schema.push_str(&self.options.name);
+        if !primary_key.is_empty() {
+            schema.push_str(" (");
+            schema.push_str(&primary_key.join(", "));
+            schema.push(')');
+        }
+        schema.push_str(" {\n");
+
+        for field in &self.fields {
+            let sql_type = sql_type(&field.kind)?;
+            let nullable = !self.is_not_null(field, &primary_key);
+            schema.push_str("        ");
+            schema.push_str(&field.options.name);
+            schema.push_str(" -> ");
+            if nullable {
+                schema.push_str(&format!("Nullable<{}>", sql_type));
+            } else {
+                schema.push_str(&sql_type);
+            }
+            schema.push_str(",\n");
+        }
+
+        schema.push_str("    }\n}\n");
+        Ok(schema)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_diesel_sql_types() {
+        assert!(matches!(Field::from_diesel_sql_type::<sql_types::Text>(), Field::Text));
+        assert!(matches!(Field::from_diesel_sql_type::<sql_types::Integer>(), Field::Integer));
+        assert!(matches!(Field::from_diesel_sql_type::<sql_types::Bool>(), Field::Boolean));
+        assert!(matches!(
+            Field::from_diesel_sql_type::<sql_types::Nullable<sql_types::Timestamptz>>(),
+            Field::Timestamp { with_timezone: true, precision: None }
+        ));
+
+        let tags = Field::from_diesel_sql_type::<sql_types::Array<sql_types::Text>>();
+        assert!(matches!(&tags, Field::Array(inner) if matches!(**inner, Field::Text)));
+    }
+
+    #[test]
+    fn small_table() {
+        let posts = TableDefn::builder("posts")
+            .column("id", Field::Serial, |c| c.primary_key())
+            .column("title", Field::Text, |c| c.not_null())
+            .column("body", Field::Text, |c| c)
+            .column("tags", Field::Array(Box::new(Field::VarChar { max_length: Some(20) })), |c| c.not_null())
+            .column("published_at", Field::Timestamp { with_timezone: true, precision: None }, |c| c.nullable())
+            .build();
+        let expected = "table! {\n    posts (id) {\n        id -> Integer,\n        title -> Text,\n        body -> Nullable<Text>,\n        tags -> Array<Varchar>,\n        published_at -> Nullable<Timestamptz>,\n    }\n}\n";
+        assert_eq!(posts.to_diesel_schema().unwrap(), expected);
+    }
+
+    #[test]
+    fn composite_key_and_unsupported_type() {
+        let memberships = TableDefn::builder("memberships")
+            .schema("app")
+            .primary_key(["user_id", "group_id"])
+            .column("user_id", Field::BigInt, |c| c)
+            .column("group_id", Field::BigInt, |c| c)
+            .build();
+        let expected = "table! {\n    app.memberships (user_id, group_id) {\n        user_id -> BigInt,\n        group_id -> BigInt,\n    }\n}\n";
+        assert_eq!(memberships.to_diesel_schema().unwrap(), expected);
+
+        let flags = TableDefn::builder("flags")
+            .column("bits", Field::Bit { length: 8 }, |c| c)
+            .build();
+        assert!(matches!(flags.to_diesel_schema(), Err(Error::InvalidSchema(_))));
+    }
+}
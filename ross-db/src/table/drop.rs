@@ -0,0 +1,114 @@
+use std::fmt;
+
+use diesel::pg::Pg;
+
+use crate::Error;
+use crate::table::fields::{IntoSql, SqlWrite, TableDefn};
+use crate::table::format::FormatOptions;
+
+
+/// What happens to objects that depend on the one being dropped.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DropBehavior {
+    /// Drop the dependent objects too.
+    Cascade,
+    /// Refuse to drop if anything depends on it, which is also what happens without either keyword.
+    Restrict,
+}
+
+impl IntoSql<Pg> for DropBehavior {
+    fn into_sql_fmt(&self, writer: &mut dyn fmt::Write) -> Result<usize, Error> {
+        let behavior = match self {
+            Self::Cascade => "CASCADE",
+            Self::Restrict => "RESTRICT",
+        };
+        writer.write_sql(behavior)
+    }
+}
+
+
+/// A `DROP TABLE` statement.
+#[derive(Debug, Default)]
+pub struct DropTable {
+    pub name: String,
+    pub if_exists: bool,
+    /// Left out of the statement when `None`.
+    pub drop_behavior: Option<DropBehavior>,
+}
+
+impl TableDefn {
+    /// The `DROP TABLE` statement that tears this table down.
+    pub fn drop_table(&self) -> DropTable {
+        DropTable {
+            name: self.quoted_name().into_owned(),
+            ..Default::default()
+        }
+    }
+}
+
+impl DropTable {
+    /// Render the `DROP TABLE` statement, spelling keywords as `format` asks.
+    pub fn into_sql_with_options(&self, writer: &mut dyn fmt::Write, format: &FormatOptions) -> Result<usize, Error> {
+        let mut total_bytes = 0;
+        total_bytes += format.keyword(writer, "DROP TABLE ")?;
+        if self.if_exists {
+            total_bytes += format.keyword(writer, "IF EXISTS ")?;
+        }
+        total_bytes += writer.write_sql(&self.name)?;
+        if let Some(drop_behavior) = &self.drop_behavior {
+            total_bytes += writer.write_sql(" ")?;
+            total_bytes += format.keywords(writer, drop_behavior)?;
+        }
+        Ok(total_bytes)
+    }
+}
+
+impl IntoSql<Pg> for DropTable {
+    fn into_sql_fmt(&self, writer: &mut dyn fmt::Write) -> Result<usize, Error> {
+        self.into_sql_with_options(writer, &FormatOptions::default())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::table::posts;
+
+    #[test]
+    fn drop_table() {
+        let (observed, _) = IntoSql::<Pg>::into_sql_str(&posts().drop_table()).unwrap();
+        assert_eq!(observed, "DROP TABLE posts");
+    }
+
+    #[test]
+    fn drop_table_if_exists() {
+        let drop = DropTable { if_exists: true, ..posts().drop_table() };
+        let (observed, _) = IntoSql::<Pg>::into_sql_str(&drop).unwrap();
+        assert_eq!(observed, "DROP TABLE IF EXISTS posts");
+    }
+
+    #[test]
+    fn drop_table_cascade() {
+        let drop = DropTable { drop_behavior: Some(DropBehavior::Cascade), ..posts().drop_table() };
+        let (observed, _) = IntoSql::<Pg>::into_sql_str(&drop).unwrap();
+        assert_eq!(observed, "DROP TABLE posts CASCADE");
+    }
+
+    #[test]
+    fn lowercase_drop_table() {
+        use crate::table::format::KeywordCase;
+
+        let drop = DropTable { if_exists: true, drop_behavior: Some(DropBehavior::Cascade), ..posts().drop_table() };
+        let mut observed = String::new();
+        drop.into_sql_with_options(&mut observed, &FormatOptions { keyword_case: KeywordCase::Lower, ..Default::default() }).unwrap();
+        assert_eq!(observed, "drop table if exists posts cascade");
+    }
+
+    #[test]
+    fn drop_table_restrict() {
+        let drop = DropTable { if_exists: true, drop_behavior: Some(DropBehavior::Restrict), ..posts().drop_table() };
+        let (observed, _) = IntoSql::<Pg>::into_sql_str(&drop).unwrap();
+        assert_eq!(observed, "DROP TABLE IF EXISTS posts RESTRICT");
+    }
+}
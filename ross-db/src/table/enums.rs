@@ -0,0 +1,151 @@
+use std::fmt;
+
+use diesel::pg::Pg;
+
+use crate::Error;
+use crate::table::fields::{Field, IntoSql, SqlWrite};
+use crate::table::format::FormatOptions;
+use crate::table::quote;
+
+
+/// A `CREATE TYPE ... AS ENUM` statement, referenced by columns through [`Field::Enum`].
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct EnumType {
+    pub name: String,
+    /// The schema the type is created in, rendered as `schema.name` when set.
+    pub schema: Option<String>,
+    pub variants: Vec<String>,
+    /// Double-quote the schema and type name if they are reserved words or contain special
+    /// characters, matching the tables whose columns use the type.
+    pub quote_identifiers: bool,
+}
+
+impl EnumType {
+    pub fn new<I, S>(name: impl Into<String>, variants: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            name: name.into(),
+            schema: None,
+            variants: variants.into_iter().map(Into::into).collect(),
+            quote_identifiers: false,
+        }
+    }
+
+    /// A [`Field::Enum`] referencing this type, qualified the same way it was created.
+    pub fn field(&self) -> Field {
+        Field::Enum { type_name: self.name.clone(), schema: self.schema.clone() }
+    }
+}
+
+impl EnumType {
+    /// Render the `CREATE TYPE` statement, spelling keywords as `format` asks.
+    pub fn into_sql_with_options(&self, writer: &mut dyn fmt::Write, format: &FormatOptions) -> Result<usize, Error> {
+        let mut total_bytes = 0;
+        total_bytes += format.keyword(writer, "CREATE TYPE ")?;
+        if let Some(schema) = &self.schema {
+            total_bytes += writer.write_sql(&quote::identifier(schema, self.quote_identifiers))?;
+            total_bytes += writer.write_sql(".")?;
+        }
+        total_bytes += writer.write_sql(&quote::identifier(&self.name, self.quote_identifiers))?;
+        total_bytes += format.keyword(writer, " AS ENUM (")?;
+        for (index, variant) in self.variants.iter().enumerate() {
+            if index > 0 {
+                total_bytes += writer.write_sql(", ")?;
+            }
+            total_bytes += writer.write_sql(&quote::literal(variant))?;
+        }
+        total_bytes += writer.write_sql(")")?;
+        Ok(total_bytes)
+    }
+}
+
+impl IntoSql<Pg> for EnumType {
+    fn into_sql_fmt(&self, writer: &mut dyn fmt::Write) -> Result<usize, Error> {
+        self.into_sql_with_options(writer, &FormatOptions::default())
+    }
+}
+
+impl fmt::Display for EnumType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        IntoSql::<Pg>::into_sql_fmt(self, f)
+            .map(|_| ())
+            .map_err(|_| fmt::Error)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::table::fields::{CommonFieldOptions, CommonTableOptions, TableDefn, TableField};
+
+    #[test]
+    fn enum_type() {
+        let mood = EnumType::new("mood", ["sad", "ok", "it's fine"]);
+        let (observed, _) = IntoSql::<Pg>::into_sql_str(&mood).unwrap();
+        assert_eq!(observed, "CREATE TYPE mood AS ENUM ('sad', 'ok', 'it''s fine')");
+    }
+
+    #[test]
+    fn enum_column() {
+        let people = TableDefn {
+            options: CommonTableOptions { name: "people".to_string(), ..Default::default() },
+            fields: vec![
+                TableField {
+                    options: CommonFieldOptions { name: "current_mood".to_string(), null: Some(false), ..Default::default() },
+                    kind: Field::Enum { type_name: "mood".to_string(), schema: None }
+                },
+            ],
+        };
+        let (observed, _) = IntoSql::<Pg>::into_sql_str(&people).unwrap();
+        assert_eq!(observed, "CREATE TABLE people (\n\tcurrent_mood mood NOT NULL\n)");
+        let parsed = TableDefn::parse(&observed).unwrap();
+        assert_eq!(parsed.fields[0].kind, Field::Enum { type_name: "mood".to_string(), schema: None });
+    }
+
+    #[test]
+    fn schema_qualified_enum_column() {
+        let mood = EnumType { schema: Some("app".to_string()), ..EnumType::new("mood", ["sad", "ok"]) };
+        let (observed, _) = IntoSql::<Pg>::into_sql_str(&mood).unwrap();
+        assert_eq!(observed, "CREATE TYPE app.mood AS ENUM ('sad', 'ok')");
+
+        let people = TableDefn {
+            options: CommonTableOptions { name: "people".to_string(), ..Default::default() },
+            fields: vec![
+                TableField {
+                    options: CommonFieldOptions { name: "current_mood".to_string(), ..Default::default() },
+                    kind: mood.field()
+                },
+            ],
+        };
+        let (observed, _) = IntoSql::<Pg>::into_sql_str(&people).unwrap();
+        assert_eq!(observed, "CREATE TABLE people (\n\tcurrent_mood app.mood\n)");
+        let parsed = TableDefn::parse(&observed).unwrap();
+        assert_eq!(parsed.fields[0].kind, Field::Enum { type_name: "mood".to_string(), schema: Some("app".to_string()) });
+    }
+    #[test]
+    fn quoted_enum_type() {
+        let mood = EnumType { schema: Some("App".to_string()), quote_identifiers: true, ..EnumType::new("user", ["sad", "ok"]) };
+        let (observed, _) = IntoSql::<Pg>::into_sql_str(&mood).unwrap();
+        assert_eq!(observed, "CREATE TYPE \"App\".\"user\" AS ENUM ('sad', 'ok')");
+
+        let people = TableDefn {
+            options: CommonTableOptions { name: "people".to_string(), quote_identifiers: true, ..Default::default() },
+            fields: vec![
+                TableField {
+                    options: CommonFieldOptions { name: "current_mood".to_string(), ..Default::default() },
+                    kind: mood.field()
+                },
+            ],
+        };
+        let (observed, _) = IntoSql::<Pg>::into_sql_str(&people).unwrap();
+        assert_eq!(observed, "CREATE TABLE people (\n\tcurrent_mood \"App\".\"user\"\n)");
+        let parsed = TableDefn::parse(&observed).unwrap();
+        assert_eq!(parsed.fields[0].kind, mood.field());
+    }
+}
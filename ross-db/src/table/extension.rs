@@ -0,0 +1,57 @@
+use std::fmt;
+
+use diesel::pg::Pg;
+
+use crate::Error;
+use crate::table::fields::{IntoSql, SqlWrite};
+use crate::table::quote;
+
+
+/// A `CREATE EXTENSION` statement, e.g. for `pgcrypto` or `pg_trgm`.
+#[derive(Debug, Clone, Default)]
+pub struct Extension {
+    /// Always double-quoted, since extension names such as `uuid-ossp` are often not plain identifiers.
+    pub name: String,
+    pub if_not_exists: bool,
+    pub schema: Option<String>,
+}
+
+impl IntoSql<Pg> for Extension {
+    fn into_sql_fmt(&self, writer: &mut dyn fmt::Write) -> Result<usize, Error> {
+        let mut total_bytes = 0;
+        total_bytes += writer.write_sql("CREATE EXTENSION ")?;
+        if self.if_not_exists {
+            total_bytes += writer.write_sql("IF NOT EXISTS ")?;
+        }
+        total_bytes += writer.write_sql_fmt(format_args!("\"{}\"", self.name.replace('"', "\"\"")))?;
+        if let Some(schema) = &self.schema {
+            total_bytes += writer.write_sql(" WITH SCHEMA ")?;
+            total_bytes += writer.write_sql(&quote::identifier(schema, true))?;
+        }
+        Ok(total_bytes)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn minimal_extension() {
+        let extension = Extension { name: "pg_trgm".to_string(), ..Default::default() };
+        let (observed, _) = IntoSql::<Pg>::into_sql_str(&extension).unwrap();
+        assert_eq!(observed, "CREATE EXTENSION \"pg_trgm\"");
+    }
+
+    #[test]
+    fn schema_qualified_extension() {
+        let extension = Extension {
+            name: "pgcrypto".to_string(),
+            if_not_exists: true,
+            schema: Some("public".to_string()),
+        };
+        let (observed, _) = IntoSql::<Pg>::into_sql_str(&extension).unwrap();
+        assert_eq!(observed, "CREATE EXTENSION IF NOT EXISTS \"pgcrypto\" WITH SCHEMA public");
+    }
+}
@@ -1,174 +1,884 @@
-use std::io::Write;
-use std::string;
+use std::borrow::Cow;
+use std::collections::HashSet;
+use std::fmt;
+use std::io;
 
-use diesel::backend::{Backend, self};
-use diesel::backend::SqlDialect;
+use diesel::backend::Backend;
+use diesel::connection::Connection;
+use diesel::query_builder::SqlQuery;
+use diesel::query_dsl::methods::ExecuteDsl;
+use diesel::result::QueryResult;
+use diesel::RunQueryDsl;
 use diesel::pg::{self, Pg};
-use diesel::sql_types::{
-    Serial,
-    Char,
-    VarChar,
-};
-use strum_macros::AsRefStr;
+
+use crate::Error;
+use crate::table::constraints::{ExcludeConstraint, ForeignKeyRef, TableForeignKey};
+use crate::table::format::FormatOptions;
+use crate::table::partition::PartitionSpec;
+use crate::table::quote;
 
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "type", content = "options"))]
 pub enum Field {
+    /// `CHAR(n)`, or bare `CHAR` without a length, which the server takes as `CHAR(1)`.
     Char {
-        max_length: usize,
+        max_length: Option<usize>,
     },
-    VarChar,
+    VarChar {
+        max_length: Option<usize>,
+    },
+    SmallSerial,
     Serial,
-    BigInt,
     BigSerial,
+    SmallInt,
+    Integer,
+    BigInt,
+    Real,
+    DoublePrecision,
+    Numeric {
+        precision: u32,
+        scale: Option<u32>,
+    },
+    Money,
     Text,
     Boolean,
+    Date,
+    Time {
+        with_timezone: bool,
+        /// Fractional-second digits, 0 to 6, e.g. `TIME(3)`.
+        precision: Option<u8>,
+    },
+    Timestamp {
+        with_timezone: bool,
+        /// Fractional-second digits, 0 to 6, e.g. `TIMESTAMP(3)`.
+        precision: Option<u8>,
+    },
+    Uuid,
+    Json,
+    Jsonb,
+    Bytea,
+    Array(Box<Field>),
+    Inet,
+    Cidr,
+    MacAddr,
     Bit {
         length: usize
     },
+    /// `INTERVAL [fields][(precision)]`, e.g. `INTERVAL DAY TO SECOND(3)`.
+    Interval {
+        /// A field restriction such as `YEAR TO MONTH`, written verbatim.
+        fields: Option<String>,
+        /// Fractional-second digits, 0 to 6.
+        precision: Option<u8>,
+    },
+    /// `BIT VARYING(n)`, or unbounded `BIT VARYING` without a length.
+    BitVarying {
+        length: Option<usize>,
+    },
+    /// A column of a user-defined enum type, see [`EnumType`](crate::table::enums::EnumType).
+    Enum {
+        type_name: String,
+        /// The schema the type lives in, rendered as `schema.type_name` when set.
+        schema: Option<String>,
+    },
+}
+
+impl Field {
+    /// Reject lengths the server would refuse, such as `CHAR(0)`.
+    pub fn validate(&self) -> Result<(), Error> {
+        match self {
+            Self::Char { max_length: Some(0) }
+            | Self::VarChar { max_length: Some(0) }
+            | Self::Bit { length: 0 }
+            | Self::BitVarying { length: Some(0) } => {
+                Err(Error::InvalidSchema(format!("{:?} must have a positive length", self)))
+            },
+            Self::Time { precision: Some(precision), .. }
+            | Self::Timestamp { precision: Some(precision), .. }
+            | Self::Interval { precision: Some(precision), .. } if *precision > 6 => {
+                Err(Error::InvalidSchema(format!("{:?} must have a precision between 0 and 6", self)))
+            },
+            Self::Array(inner) => inner.validate(),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl Field {
+    pub(crate) fn write_pg(&self, writer: &mut dyn fmt::Write, quote_identifiers: bool, format: &FormatOptions) -> Result<usize, Error> {
+        self.validate()?;
+        match self {
+            Self::Char { max_length: Some(max_length) } => write_parameterized(writer, format, format.char_spelling.keyword(), format_args!("({})", *max_length)),
+            Self::Char { max_length: None } => format.keyword(writer, format.char_spelling.keyword()),
+            Self::VarChar { max_length: Some(max_length) } => write_parameterized(writer, format, "VARCHAR", format_args!("({})", *max_length)),
+            Self::VarChar { max_length: None } => format.keyword(writer, "VARCHAR"),
+            Self::Text => format.keyword(writer, "TEXT"),
+            Self::SmallSerial => format.keyword(writer, "SMALLSERIAL"),
+            Self::Serial => format.keyword(writer, "SERIAL"),
+            Self::BigSerial => format.keyword(writer, "BIGSERIAL"),
+            Self::SmallInt => format.keyword(writer, "SMALLINT"),
+            Self::Integer => format.keyword(writer, "INTEGER"),
+            Self::BigInt => format.keyword(writer, "BIGINT"),
+            Self::Real => format.keyword(writer, "REAL"),
+            Self::DoublePrecision => format.keyword(writer, "DOUBLE PRECISION"),
+            Self::Numeric { precision, scale: Some(scale) } => write_parameterized(writer, format, "NUMERIC", format_args!("({}, {})", *precision, *scale)),
+            Self::Numeric { precision, scale: None } => write_parameterized(writer, format, "NUMERIC", format_args!("({})", *precision)),
+            Self::Money => format.keyword(writer, "MONEY"),
+            Self::Boolean => format.keyword(writer, "BOOLEAN"),
+            Self::Date => format.keyword(writer, "DATE"),
+            Self::Time { with_timezone, precision } => write_datetime(writer, format, "TIME", *precision, *with_timezone),
+            Self::Timestamp { with_timezone, precision } => write_datetime(writer, format, "TIMESTAMP", *precision, *with_timezone),
+            Self::Uuid => format.keyword(writer, "UUID"),
+            Self::Json => format.keyword(writer, "JSON"),
+            Self::Jsonb => format.keyword(writer, "JSONB"),
+            Self::Bytea => format.keyword(writer, "BYTEA"),
+            Self::Inet => format.keyword(writer, "INET"),
+            Self::Cidr => format.keyword(writer, "CIDR"),
+            Self::MacAddr => format.keyword(writer, "MACADDR"),
+            Self::Array(inner) => {
+                let mut total_bytes = inner.write_pg(writer, quote_identifiers, format)?;
+                total_bytes += writer.write_sql("[]")?;
+                Ok(total_bytes)
+            },
+            Self::Bit { length } => write_parameterized(writer, format, "BIT", format_args!("({})", *length)),
+            Self::BitVarying { length: Some(length) } => write_parameterized(writer, format, "BIT VARYING", format_args!("({})", *length)),
+            Self::BitVarying { length: None } => format.keyword(writer, "BIT VARYING"),
+            Self::Interval { fields, precision } => {
+                let mut total_bytes = format.keyword(writer, "INTERVAL")?;
+                if let Some(fields) = fields {
+                    total_bytes += writer.write_sql(" ")?;
+                    total_bytes += writer.write_sql(fields)?;
+                }
+                if let Some(precision) = precision {
+                    total_bytes += writer.write_sql_fmt(format_args!("({})", precision))?;
+                }
+                Ok(total_bytes)
+            },
+            Self::Enum { type_name, schema } => {
+                let mut total_bytes = 0;
+                if let Some(schema) = schema {
+                    total_bytes += writer.write_sql(&quote::identifier(schema, quote_identifiers))?;
+                    total_bytes += writer.write_sql(".")?;
+                }
+                total_bytes += writer.write_sql(&quote::identifier(type_name, quote_identifiers))?;
+                Ok(total_bytes)
+            },
+        }
+    }
 }
 
+impl Field {
+    pub(crate) fn is_serial(&self) -> bool {
+        matches!(self, Self::SmallSerial | Self::Serial | Self::BigSerial)
+    }
+}
 
 impl IntoSql<pg::Pg> for Field {
-    fn into_sql<W: Write>(&self, writer: &mut W) -> Result<usize, Box<dyn std::error::Error>> {
-        let data_type = match self {
-            Self::Char { max_length } => format!("CHAR({})", *max_length),
-            Self::VarChar => "VARCHAR".into(),
-            Self::Text => "TEXT".into(),
-            Self::Serial => "SERIAL".into(),
-            Self::BigInt => "BIGINT".into(),
-            Self::BigSerial => "BIGSERIAL".into(),
-            Self::Boolean => "BOOLEAN".into(),
-            Self::Bit { length } => format!("BIT({})", *length),
-        };
+    fn into_sql_fmt(&self, writer: &mut dyn fmt::Write) -> Result<usize, Error> {
+        self.write_pg(writer, false, &FormatOptions::default())
+    }
+}
 
-        writer
-        .write(data_type.as_bytes())
-        .map_err(|err| err.into())
+fn write_parameterized(writer: &mut dyn fmt::Write, format: &FormatOptions, type_name: &str, arguments: fmt::Arguments) -> Result<usize, Error> {
+    Ok(format.keyword(writer, type_name)? + writer.write_sql_fmt(arguments)?)
+}
+
+fn write_datetime(writer: &mut dyn fmt::Write, format: &FormatOptions, type_name: &str, precision: Option<u8>, with_timezone: bool) -> Result<usize, Error> {
+    let mut total_bytes = format.keyword(writer, type_name)?;
+    if let Some(precision) = precision {
+        total_bytes += writer.write_sql_fmt(format_args!("({})", precision))?;
     }
+    if with_timezone {
+        total_bytes += format.keyword(writer, " WITH TIME ZONE")?;
+    }
+    Ok(total_bytes)
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TableField {
-    options: CommonFieldOptions,
-    kind: Field
+    pub options: CommonFieldOptions,
+    pub kind: Field
 }
 
-impl IntoSql<Pg> for TableField {
-    fn into_sql<W: Write>(&self, writer: &mut W) -> Result<usize, Box<dyn std::error::Error>> {
+impl TableField {
+    pub(crate) fn validate(&self) -> Result<(), Error> {
+        if self.options.generated.is_some() && (self.options.default.is_some() || self.options.identity.is_some()) {
+            return Err(Error::InvalidSchema(format!(
+                "generated column {} cannot also have a default or be an identity column",
+                self.options.name
+            )));
+        }
+        if self.options.generated.as_ref().is_some_and(|generated| !generated.stored) {
+            return Err(Error::InvalidSchema(format!("generated column {} must be stored", self.options.name)));
+        }
+        if self.options.allowed_values.as_ref().is_some_and(Vec::is_empty) {
+            return Err(Error::InvalidSchema(format!("column {} must allow at least one value", self.options.name)));
+        }
+        Ok(())
+    }
+
+    pub(crate) fn write_pg(&self, writer: &mut dyn fmt::Write, inline_primary_key: bool, quote_identifiers: bool, format: &FormatOptions) -> Result<usize, Error> {
+        self.validate()?;
+
         let mut total_bytes = 0;
-        total_bytes += writer.write(self.options.name.as_bytes())?;
-        total_bytes += writer.write(" ".as_bytes())?;
+        total_bytes += writer.write_sql(&quote::identifier(&self.options.name, quote_identifiers))?;
+        total_bytes += writer.write_sql(" ")?;
+
+        total_bytes += self.kind.write_pg(writer, quote_identifiers, format)?;
+
+        if let Some(collation) = &self.options.collation {
+            total_bytes += format.keyword(writer, " COLLATE ")?;
+            total_bytes += writer.write_sql("\"")?;
+            total_bytes += writer.write_sql(&collation.replace('"', "\"\""))?;
+            total_bytes += writer.write_sql("\"")?;
+        }
+
+        if let Some(default) = &self.options.default {
+            total_bytes += format.keyword(writer, " DEFAULT ")?;
+            total_bytes += writer.write_sql(default)?;
+        }
+
+        if let Some(identity) = &self.options.identity {
+            total_bytes += writer.write_sql(" ")?;
+            total_bytes += format.keywords(writer, identity)?;
+        }
+
+        if let Some(generated) = &self.options.generated {
+            total_bytes += writer.write_sql(" ")?;
+            total_bytes += generated.write_pg(writer, format)?;
+        }
 
-        total_bytes += self.kind.into_sql(writer)?;
-        total_bytes += writer.write(" ".as_bytes())?;
-        
+        let mut constraints = Vec::new();
         if let Some(null_constraint) = self.options.null {
-            let value = if null_constraint {
+            constraints.push(format.cased(if null_constraint {
                 "NULL"
             } else {
                 "NOT NULL"
-            };
-            total_bytes += writer.write(value.as_bytes())?;
-            total_bytes += writer.write(" ".as_bytes())?;
+            }));
         }
-
-        if self.options.primary_key {
-            total_bytes += writer.write("PRIMARY KEY".as_bytes())?;
-            total_bytes += writer.write(" ".as_bytes())?;
+        if self.options.primary_key && inline_primary_key {
+            if let Some(name) = &self.options.primary_key_constraint {
+                constraints.extend([format.cased("CONSTRAINT"), Cow::Borrowed(name.as_str())]);
+            }
+            constraints.push(format.cased("PRIMARY KEY"));
         }
         if self.options.unique {
-            total_bytes += writer.write("UNIQUE".as_bytes())?;
-            total_bytes += writer.write(" ".as_bytes())?;
+            if let Some(name) = &self.options.unique_constraint {
+                constraints.extend([format.cased("CONSTRAINT"), Cow::Borrowed(name.as_str())]);
+            }
+            constraints.push(format.cased("UNIQUE"));
+        }
+
+        if !constraints.is_empty() {
+            total_bytes += writer.write_sql(" ")?;
+            total_bytes += writer.write_sql(&constraints.join(" "))?;
+        }
+
+        if let Some(check) = &self.options.check {
+            if let Some(name) = &self.options.check_constraint {
+                total_bytes += format.keyword(writer, " CONSTRAINT ")?;
+                total_bytes += writer.write_sql(name)?;
+            }
+            total_bytes += format.keyword(writer, " CHECK (")?;
+            total_bytes += writer.write_sql(check)?;
+            total_bytes += writer.write_sql(")")?;
+        }
+
+        if let Some(values) = &self.options.allowed_values {
+            total_bytes += format.keyword(writer, " CHECK (")?;
+            total_bytes += writer.write_sql(&quote::identifier(&self.options.name, quote_identifiers))?;
+            total_bytes += format.keyword(writer, " IN (")?;
+            total_bytes += writer.write_sql(&quote::literal_list(values))?;
+            total_bytes += writer.write_sql("))")?;
+        }
+
+        if let Some(references) = &self.options.references {
+            total_bytes += writer.write_sql(" ")?;
+            total_bytes += references.write_pg(writer, quote_identifiers, format)?;
         }
 
         Ok(total_bytes)
     }
 }
 
+impl IntoSql<Pg> for TableField {
+    fn into_sql_fmt(&self, writer: &mut dyn fmt::Write) -> Result<usize, Error> {
+        self.write_pg(writer, true, false, &FormatOptions::default())
+    }
+}
+
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct CommonFieldOptions {
-    name: String,
-    primary_key: bool,
-    unique: bool,
-    null: Option<bool>,
+    pub name: String,
+    pub primary_key: bool,
+    pub unique: bool,
+    pub null: Option<bool>,
+    pub references: Option<ForeignKeyRef>,
+    /// An expression written verbatim after `DEFAULT`, e.g. `now()` or `'{}'::jsonb`.
+    pub default: Option<String>,
+    /// A boolean expression written verbatim inside `CHECK (...)`.
+    pub check: Option<String>,
+    /// Rendered as a separate `CHECK (name IN ('a', 'b', ...))`, the name quoted like the column's own.
+    pub allowed_values: Option<Vec<String>>,
+    /// Rendered as `COLLATE "<collation>"` right after the type.
+    pub collation: Option<String>,
+    pub identity: Option<IdentityKind>,
+    pub generated: Option<GeneratedColumn>,
+    /// Emitted as a separate `COMMENT ON COLUMN` statement.
+    pub comment: Option<String>,
+    /// Names the inline `PRIMARY KEY` as `CONSTRAINT <name> PRIMARY KEY`.
+    pub primary_key_constraint: Option<String>,
+    /// Names the inline `UNIQUE` as `CONSTRAINT <name> UNIQUE`.
+    pub unique_constraint: Option<String>,
+    /// Names the inline `CHECK` as `CONSTRAINT <name> CHECK (...)`.
+    pub check_constraint: Option<String>,
+}
+
+/// A column computed from other columns, `GENERATED ALWAYS AS (<expression>)`.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GeneratedColumn {
+    /// Written verbatim inside the parentheses.
+    pub expression: String,
+    /// Must be set: Postgres only computes generated columns on write, as `STORED`.
+    pub stored: bool,
+}
+
+impl GeneratedColumn {
+    pub(crate) fn write_pg(&self, writer: &mut dyn fmt::Write, format: &FormatOptions) -> Result<usize, Error> {
+        let mut total_bytes = 0;
+        total_bytes += format.keyword(writer, "GENERATED ALWAYS AS (")?;
+        total_bytes += writer.write_sql(&self.expression)?;
+        total_bytes += writer.write_sql(")")?;
+        if self.stored {
+            total_bytes += format.keyword(writer, " STORED")?;
+        }
+        Ok(total_bytes)
+    }
+}
+
+impl IntoSql<Pg> for GeneratedColumn {
+    fn into_sql_fmt(&self, writer: &mut dyn fmt::Write) -> Result<usize, Error> {
+        self.write_pg(writer, &FormatOptions::default())
+    }
+}
+
+/// How an identity column generates its values.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum IdentityKind {
+    Always,
+    ByDefault,
 }
 
-#[derive(Debug, AsRefStr)]
+impl IntoSql<Pg> for IdentityKind {
+    fn into_sql_fmt(&self, writer: &mut dyn fmt::Write) -> Result<usize, Error> {
+        let identity = match self {
+            Self::Always => "GENERATED ALWAYS AS IDENTITY",
+            Self::ByDefault => "GENERATED BY DEFAULT AS IDENTITY",
+        };
+        writer.write_sql(identity)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum TableKind {
     Global,
     Local
 }
 
-impl<T> IntoSql<pg::Pg> for T 
-where
-    T: AsRef<str>
-{
-    fn into_sql<W: Write>(&self, writer: &mut W) -> Result<usize, Box<dyn std::error::Error>> {
-        writer.write(self.as_ref().to_uppercase().as_bytes()).map_err(|err| err.into())
+impl IntoSql<pg::Pg> for TableKind {
+    fn into_sql_fmt(&self, writer: &mut dyn fmt::Write) -> Result<usize, Error> {
+        let kind = match self {
+            Self::Global => "GLOBAL",
+            Self::Local => "LOCAL",
+        };
+        writer.write_sql(kind)
     }
 }
 
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct CommonTableOptions {
-    name: String,
-    if_not_exists: bool,
-    kind: Option<TableKind>,
+    pub name: String,
+    /// The schema the table lives in, rendered as `schema.name` when set.
+    pub schema: Option<String>,
+    pub if_not_exists: bool,
+    pub kind: Option<TableKind>,
+    pub unlogged: bool,
+    /// Double-quote table and column names that are reserved words or contain special characters.
+    pub quote_identifiers: bool,
+    /// The columns of a table-level `PRIMARY KEY (...)` constraint, for composite keys.
+    pub primary_key: Vec<String>,
+    /// Table-level `UNIQUE (...)` constraints as `(constraint name, columns)` pairs.
+    pub unique_constraints: Vec<(Option<String>, Vec<String>)>,
+    /// Table-level, possibly multi-column, foreign keys.
+    pub foreign_keys: Vec<TableForeignKey>,
+    /// Table-level `CHECK` constraints as `(constraint name, expression)` pairs.
+    pub checks: Vec<(Option<String>, String)>,
+    pub exclusions: Vec<ExcludeConstraint>,
+    /// Emitted as a separate `COMMENT ON TABLE` statement.
+    pub comment: Option<String>,
+    /// Parent tables, rendered as `INHERITS (...)` after the column list.
+    pub inherits: Vec<String>,
+    pub partition_by: Option<PartitionSpec>,
+    /// Storage parameters rendered as `WITH (key = value, ...)`, values written verbatim.
+    pub storage_params: Vec<(String, String)>,
+    pub tablespace: Option<String>,
+    /// Follow the create with `ALTER TABLE ... ENABLE ROW LEVEL SECURITY`.
+    pub row_level_security: bool,
+    /// Follow the create with `ALTER TABLE ... FORCE ROW LEVEL SECURITY`, applying policies to the owner too.
+    pub force_rls: bool,
+    /// Follow the create with `ALTER TABLE ... OWNER TO <owner>`.
+    pub owner: Option<String>,
+}
+
+impl CommonTableOptions {
+    /// Options for a table called `name`, everything else left at its default.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            ..Default::default()
+        }
+    }
+}
+
+impl CommonFieldOptions {
+    /// Options for a column called `name`, everything else left at its default.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            ..Default::default()
+        }
+    }
+}
+
+impl TableField {
+    /// A column called `name` of type `kind`, see [`CommonFieldOptions`] for everything else.
+    ///
+    /// ```
+    /// use ross_db::table::fields::{CommonFieldOptions, CommonTableOptions, Field, TableDefn, TableField};
+    ///
+    /// let mut title = TableField::new("title", Field::Text);
+    /// title.options.null = Some(false);
+    /// let posts = TableDefn {
+    ///     options: CommonTableOptions::new("posts"),
+    ///     fields: vec![
+    ///         TableField {
+    ///             options: CommonFieldOptions { primary_key: true, ..CommonFieldOptions::new("id") },
+    ///             kind: Field::Serial,
+    ///         },
+    ///         title,
+    ///     ],
+    /// };
+    /// assert_eq!(posts.to_string(), "CREATE TABLE posts (\n\tid SERIAL PRIMARY KEY,\n\ttitle TEXT NOT NULL\n)");
+    /// ```
+    pub fn new(name: impl Into<String>, kind: Field) -> Self {
+        Self {
+            options: CommonFieldOptions::new(name),
+            kind,
+        }
+    }
 }
 
 
+#[allow(clippy::wrong_self_convention)]
 pub trait IntoSql<B: Backend> {
-    fn into_sql<W: Write>(&self, writer: &mut W) -> Result<usize, Box<dyn std::error::Error>>;
+    /// Render into any `fmt::Write`, returning the number of bytes written.
+    fn into_sql_fmt(&self, writer: &mut dyn fmt::Write) -> Result<usize, Error>;
 
-    fn into_sql_str(&self) -> Result<(String, usize), Box<dyn std::error::Error>> {
-        let mut writer = Vec::new();
-        let bytes_written = self.into_sql(&mut writer)?;
-        Ok((String::from_utf8(writer)?, bytes_written))
+    /// Stream the SQL into an `io::Write` such as a file or socket.
+    fn into_sql(&self, writer: &mut dyn io::Write) -> Result<usize, Error> {
+        write_io(writer, |writer| self.into_sql_fmt(writer))
+    }
+
+    fn into_sql_str(&self) -> Result<(String, usize), Error> {
+        let mut sql = String::new();
+        let bytes_written = self.into_sql_fmt(&mut sql)?;
+        Ok((sql, bytes_written))
+    }
+
+    /// Stream the SQL into a `tokio` writer, rendering it exactly as [`IntoSql::into_sql_str`] does.
+    #[cfg(feature = "tokio")]
+    #[allow(async_fn_in_trait)]
+    async fn into_sql_async<W: tokio::io::AsyncWrite + Unpin>(&self, writer: &mut W) -> Result<usize, Error>
+    where
+        Self: Sized,
+    {
+        use tokio::io::AsyncWriteExt;
+
+        let (sql, bytes_written) = self.into_sql_str()?;
+        writer.write_all(sql.as_bytes()).await?;
+        Ok(bytes_written)
+    }
+}
+
+/// Writes a piece of SQL and reports its length, so impls can keep a running byte count.
+pub trait SqlWrite: fmt::Write {
+    fn write_sql(&mut self, sql: &str) -> Result<usize, Error> {
+        self.write_str(sql)?;
+        Ok(sql.len())
+    }
+
+    /// Like [`SqlWrite::write_sql`] for `format_args!` output, without building a `String` first.
+    fn write_sql_fmt(&mut self, args: fmt::Arguments<'_>) -> Result<usize, Error> {
+        let mut counter = CountingWriter { inner: self, bytes_written: 0 };
+        fmt::Write::write_fmt(&mut counter, args)?;
+        Ok(counter.bytes_written)
+    }
+}
+
+impl<W: fmt::Write + ?Sized> SqlWrite for W {}
+
+struct CountingWriter<'a, W: fmt::Write + ?Sized> {
+    inner: &'a mut W,
+    bytes_written: usize,
+}
+
+impl<W: fmt::Write + ?Sized> fmt::Write for CountingWriter<'_, W> {
+    fn write_str(&mut self, sql: &str) -> fmt::Result {
+        self.inner.write_str(sql)?;
+        self.bytes_written += sql.len();
+        Ok(())
+    }
+}
+
+struct IoWriter<'a, W: io::Write + ?Sized> {
+    inner: &'a mut W,
+    error: Option<io::Error>,
+}
+
+impl<W: io::Write + ?Sized> fmt::Write for IoWriter<'_, W> {
+    fn write_str(&mut self, sql: &str) -> fmt::Result {
+        self.inner.write_all(sql.as_bytes()).map_err(|err| {
+            self.error = Some(err);
+            fmt::Error
+        })
     }
 }
 
+fn write_io<W, F>(writer: &mut W, render: F) -> Result<usize, Error>
+where
+    W: io::Write + ?Sized,
+    F: FnOnce(&mut IoWriter<'_, W>) -> Result<usize, Error>,
+{
+    let mut adapter = IoWriter { inner: writer, error: None };
+    render(&mut adapter).map_err(|err| match adapter.error.take() {
+        Some(io_error) => Error::Io(io_error),
+        None => err,
+    })
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TableDefn {
-    fields: Vec<TableField>,
-    options: CommonTableOptions
+    /// The columns in the order they are rendered, which is the order they were added in;
+    /// neither the builder nor the parser reorders them. See [`TableDefn::sort_columns_by_name`].
+    pub fields: Vec<TableField>,
+    pub options: CommonTableOptions
 }
 
 impl IntoSql<pg::Pg> for TableDefn {
-    fn into_sql<W: Write>(&self, writer: &mut W) -> Result<usize, Box<dyn std::error::Error>> {
-        
+    fn into_sql_fmt(&self, writer: &mut dyn fmt::Write) -> Result<usize, Error> {
+        self.into_sql_with_options(writer, &FormatOptions::default())
+    }
+}
+
+impl TableDefn {
+    /// Reject definitions no server would accept: tables without columns, or with
+    /// several columns of the same name. Duplicates are listed in the error.
+    pub fn validate(&self) -> Result<(), Error> {
+        if self.fields.is_empty() {
+            return Err(Error::InvalidSchema(format!("table {} must have at least one column", self.options.name)));
+        }
+
+        let mut seen = HashSet::new();
+        let mut duplicates = Vec::new();
+        for field in &self.fields {
+            let name = field.options.name.as_str();
+            if !seen.insert(name) && !duplicates.contains(&name) {
+                duplicates.push(name);
+            }
+        }
+        if !duplicates.is_empty() {
+            return Err(Error::InvalidSchema(format!(
+                "table {} has duplicate columns: {}",
+                self.options.name,
+                duplicates.join(", ")
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Render the `CREATE TABLE` statement with the given layout.
+    pub fn into_sql_with_options(&self, writer: &mut dyn fmt::Write, format: &FormatOptions) -> Result<usize, Error> {
+        self.validate()?;
+        if self.options.unlogged && self.options.kind.is_some() {
+            return Err(Error::InvalidSchema(format!("table {} cannot be both temporary and unlogged", self.options.name)));
+        }
+
         let mut total_bytes = 0;
-        total_bytes += writer.write("CREATE ".as_bytes())?;
+        total_bytes += format.keyword(writer, "CREATE ")?;
 
         if let Some(kind) = &self.options.kind {
-            total_bytes += kind.into_sql(writer)?;
-            total_bytes += writer.write(b" ")?;
+            total_bytes += format.keywords(writer, kind)?;
+            total_bytes += format.keyword(writer, " TEMPORARY ")?;
         };
+        if self.options.unlogged {
+            total_bytes += format.keyword(writer, "UNLOGGED ")?;
+        }
 
-        total_bytes += writer.write(self.options.name.as_bytes())?;
+        total_bytes += format.keyword(writer, "TABLE ")?;
         if self.options.if_not_exists {
-            total_bytes += writer.write(b" IF NOT EXISTS")?;
+            total_bytes += format.keyword(writer, "IF NOT EXISTS ")?;
+        }
+        total_bytes += writer.write_sql(&self.quoted_name())?;
+
+        total_bytes += format.open_list(writer)?;
+
+        let inline_primary_key = self.options.primary_key.is_empty();
+
+        for (index, field) in self.fields.iter().enumerate() {
+            if index > 0 {
+                total_bytes += format.list_separator(writer)?;
+            }
+            total_bytes += field.write_pg(writer, inline_primary_key, self.options.quote_identifiers, format)?;
+        }
+
+        if !inline_primary_key {
+            total_bytes += format.list_separator(writer)?;
+            total_bytes += format.keyword(writer, "PRIMARY KEY (")?;
+            total_bytes += writer.write_sql(&self.quoted_column_list(&self.options.primary_key))?;
+            total_bytes += writer.write_sql(")")?;
+        }
+
+        for (name, columns) in &self.options.unique_constraints {
+            total_bytes += format.list_separator(writer)?;
+            if let Some(name) = name {
+                total_bytes += format.keyword(writer, "CONSTRAINT ")?;
+                total_bytes += writer.write_sql(name)?;
+                total_bytes += writer.write_sql(" ")?;
+            }
+            total_bytes += format.keyword(writer, "UNIQUE (")?;
+            total_bytes += writer.write_sql(&self.quoted_column_list(columns))?;
+            total_bytes += writer.write_sql(")")?;
+        }
+
+        for foreign_key in &self.options.foreign_keys {
+            total_bytes += format.list_separator(writer)?;
+            total_bytes += foreign_key.write_pg(writer, self.options.quote_identifiers, format)?;
+        }
+
+        for (name, check) in &self.options.checks {
+            total_bytes += format.list_separator(writer)?;
+            if let Some(name) = name {
+                total_bytes += format.keyword(writer, "CONSTRAINT ")?;
+                total_bytes += writer.write_sql(name)?;
+                total_bytes += writer.write_sql(" ")?;
+            }
+            total_bytes += format.keyword(writer, "CHECK (")?;
+            total_bytes += writer.write_sql(check)?;
+            total_bytes += writer.write_sql(")")?;
+        }
+
+        for exclusion in &self.options.exclusions {
+            total_bytes += format.list_separator(writer)?;
+            total_bytes += exclusion.write_pg(writer, format)?;
+        }
+
+        total_bytes += format.close_list(writer)?;
+
+        if !self.options.inherits.is_empty() {
+            let parents = self
+                .options
+                .inherits
+                .iter()
+                .map(|parent| quote::identifier(parent, self.options.quote_identifiers))
+                .collect::<Vec<_>>();
+            total_bytes += format.keyword(writer, " INHERITS (")?;
+            total_bytes += writer.write_sql(&parents.join(", "))?;
+            total_bytes += writer.write_sql(")")?;
+        }
+
+        if let Some(partition_by) = &self.options.partition_by {
+            total_bytes += writer.write_sql(" ")?;
+            total_bytes += partition_by.write_pg(writer, format)?;
+        }
+
+        if !self.options.storage_params.is_empty() {
+            total_bytes += format.keyword(writer, " WITH (")?;
+            for (index, (key, value)) in self.options.storage_params.iter().enumerate() {
+                if index > 0 {
+                    total_bytes += writer.write_sql(", ")?;
+                }
+                total_bytes += writer.write_sql(key)?;
+                total_bytes += writer.write_sql(" = ")?;
+                total_bytes += writer.write_sql(value)?;
+            }
+            total_bytes += writer.write_sql(")")?;
+        }
+
+        if let Some(tablespace) = &self.options.tablespace {
+            total_bytes += format.keyword(writer, " TABLESPACE ")?;
+            total_bytes += writer.write_sql(&quote::identifier(tablespace, self.options.quote_identifiers))?;
+        }
+
+        Ok(total_bytes)
+    }
+}
+
+
+impl TableDefn {
+    pub(crate) fn quoted_name(&self) -> Cow<'_, str> {
+        let name = quote::identifier(&self.options.name, self.options.quote_identifiers);
+        match &self.options.schema {
+            Some(schema) => Cow::Owned(format!("{}.{}", quote::identifier(schema, self.options.quote_identifiers), name)),
+            None => name,
+        }
+    }
+
+    /// Order the columns by name, for output that doesn't depend on how the fields were collected,
+    /// e.g. from a `HashMap`.
+    pub fn sort_columns_by_name(&mut self) {
+        self.fields.sort_by(|a, b| a.options.name.cmp(&b.options.name));
+    }
+
+    pub(crate) fn sole_primary_key(&self) -> Option<&str> {
+        match self.options.primary_key.as_slice() {
+            [column] => Some(column),
+            [] => {
+                let mut flagged = self.fields.iter().filter(|field| field.options.primary_key);
+                match (flagged.next(), flagged.next()) {
+                    (Some(field), None) => Some(&field.options.name),
+                    _ => None,
+                }
+            },
+            _ => None,
+        }
+    }
+
+    pub(crate) fn quoted_column_list(&self, columns: &[String]) -> String {
+        columns
+            .iter()
+            .map(|column| quote::identifier(column, self.options.quote_identifiers))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    /// Render the `CREATE TABLE` statement followed by `COMMENT ON` statements for
+    /// the table and every commented column, each terminated by a semicolon.
+    pub fn into_sql_with_comments<W: io::Write>(&self, writer: &mut W, format: &FormatOptions) -> Result<usize, Error> {
+        write_io(writer, |writer| self.write_with_comments(writer, format))
+    }
+
+    /// Render the `CREATE TABLE` statement followed by the `ALTER TABLE` statements
+    /// its options call for, such as enabling row-level security or changing the owner,
+    /// each terminated by a semicolon.
+    pub fn into_sql_with_alters<W: io::Write>(&self, writer: &mut W, format: &FormatOptions) -> Result<usize, Error> {
+        write_io(writer, |writer| self.write_with_alters(writer, format))
+    }
+
+    fn write_with_alters(&self, writer: &mut dyn fmt::Write, format: &FormatOptions) -> Result<usize, Error> {
+        let mut total_bytes = self.into_sql_with_options(writer, format)?;
+        total_bytes += writer.write_sql(";")?;
+
+        let mut alters = Vec::new();
+        if self.options.row_level_security {
+            alters.push(format.cased("ENABLE ROW LEVEL SECURITY"));
+        }
+        if self.options.force_rls {
+            alters.push(format.cased("FORCE ROW LEVEL SECURITY"));
+        }
+        if let Some(owner) = &self.options.owner {
+            alters.push(Cow::Owned(format!("{} {}", format.cased("OWNER TO"), quote::identifier(owner, self.options.quote_identifiers))));
+        }
+
+        for alter in alters {
+            total_bytes += format.keyword(writer, "\nALTER TABLE ")?;
+            total_bytes += writer.write_sql(&self.quoted_name())?;
+            total_bytes += writer.write_sql(" ")?;
+            total_bytes += writer.write_sql(&alter)?;
+            total_bytes += writer.write_sql(";")?;
         }
 
-        total_bytes += writer.write(b" (\n\t")?;
+        Ok(total_bytes)
+    }
 
+    fn write_with_comments(&self, writer: &mut dyn fmt::Write, format: &FormatOptions) -> Result<usize, Error> {
+        let mut total_bytes = self.into_sql_with_options(writer, format)?;
+        total_bytes += writer.write_sql(";")?;
 
-        let num_fields = self.fields.len();
+        if let Some(comment) = &self.options.comment {
+            total_bytes += format.keyword(writer, "\nCOMMENT ON TABLE ")?;
+            total_bytes += writer.write_sql(&self.quoted_name())?;
+            total_bytes += format.keyword(writer, " IS ")?;
+            total_bytes += writer.write_sql(&quote::literal(comment))?;
+            total_bytes += writer.write_sql(";")?;
+        }
 
-        self
-        .fields
-        .iter()
-        .enumerate()
-        .for_each(|(index, field)| {
-            total_bytes += field.into_sql(writer).unwrap();
-            if index != num_fields - 1 {
-                total_bytes += writer.write(b",\n\t").unwrap();
+        for field in &self.fields {
+            if let Some(comment) = &field.options.comment {
+                total_bytes += format.keyword(writer, "\nCOMMENT ON COLUMN ")?;
+                total_bytes += writer.write_sql(&self.quoted_name())?;
+                total_bytes += writer.write_sql(".")?;
+                total_bytes += writer.write_sql(&quote::identifier(&field.options.name, self.options.quote_identifiers))?;
+                total_bytes += format.keyword(writer, " IS ")?;
+                total_bytes += writer.write_sql(&quote::literal(comment))?;
+                total_bytes += writer.write_sql(";")?;
             }
-        });
+        }
 
-        total_bytes += writer.write(b"\n)")?;
         Ok(total_bytes)
     }
+
+    /// Render the `CREATE TABLE` statement for the connection's backend and run it.
+    ///
+    /// Rendering failures surface as [`diesel::result::Error::QueryBuilderError`].
+    pub fn execute<C>(&self, conn: &mut C) -> QueryResult<usize>
+    where
+        C: Connection,
+        Self: IntoSql<C::Backend>,
+        SqlQuery: ExecuteDsl<C>,
+    {
+        let (sql, _) = IntoSql::<C::Backend>::into_sql_str(self)
+            .map_err(|err| diesel::result::Error::QueryBuilderError(Box::new(err)))?;
+        diesel::sql_query(sql).execute(conn)
+    }
+}
+
+
+impl fmt::Display for Field {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        IntoSql::<Pg>::into_sql_fmt(self, f)
+            .map(|_| ())
+            .map_err(|_| fmt::Error)
+    }
+}
+
+impl fmt::Display for TableField {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        IntoSql::<Pg>::into_sql_fmt(self, f)
+            .map(|_| ())
+            .map_err(|_| fmt::Error)
+    }
+}
+
+impl fmt::Display for TableDefn {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        IntoSql::<Pg>::into_sql_fmt(self, f)
+            .map(|_| ())
+            .map_err(|_| fmt::Error)
+    }
 }
 
 
@@ -180,14 +890,15 @@ mod tests {
     fn table() {
 
         let posts = TableDefn {
-            options: CommonTableOptions { name: "posts".to_string(), if_not_exists: true, kind: Some(TableKind::Global) },
+            options: CommonTableOptions { name: "posts".to_string(), if_not_exists: true, kind: Some(TableKind::Global), ..Default::default() },
             fields: vec![
                 TableField {
                     options: CommonFieldOptions {
                         name: "id".to_string(),
                         primary_key: true,
                         unique: false,
-                        null: None
+                        null: None,
+                        ..Default::default()
                     },
                     kind: Field::Serial
                 },
@@ -196,16 +907,18 @@ mod tests {
                         name: "title".to_string(),
                         primary_key: false,
                         unique: false,
-                        null: Some(false)
+                        null: Some(false),
+                        ..Default::default()
                     },
-                    kind: Field::Char { max_length: 10 }
+                    kind: Field::Char { max_length: Some(10) }
                 },
                 TableField {
                     options: CommonFieldOptions {
                         name: "body".to_string(),
                         primary_key: false,
                         unique: false,
-                        null: Some(false)
+                        null: Some(false),
+                        ..Default::default()
                     },
                     kind: Field::Text
                 },
@@ -215,13 +928,1139 @@ mod tests {
                         primary_key: false,
                         unique: false,
                         null: Some(false),
+                        ..Default::default()
                     },
                     kind: Field::Boolean
                 },
             ],
         };
-        let (observed, _) = posts.into_sql_str().unwrap();
-        let expected = "CREATE GLOBAL posts IF NOT EXISTS (\n\tid SERIAL PRIMARY KEY ,\n\ttitle CHAR(10) NOT NULL ,\n\tbody TEXT NOT NULL ,\n\tpublished BOOLEAN NOT NULL \n)";
+        let (observed, _) = IntoSql::<Pg>::into_sql_str(&posts).unwrap();
+        let expected = "CREATE GLOBAL TEMPORARY TABLE IF NOT EXISTS posts (\n\tid SERIAL PRIMARY KEY,\n\ttitle CHAR(10) NOT NULL,\n\tbody TEXT NOT NULL,\n\tpublished BOOLEAN NOT NULL\n)";
+        assert_eq!(observed, expected);
+    }
+
+    #[test]
+    fn table_integer_columns() {
+        let counters = TableDefn {
+            options: CommonTableOptions { name: "counters".to_string(), if_not_exists: false, kind: None, ..Default::default() },
+            fields: vec![
+                TableField {
+                    options: CommonFieldOptions {
+                        name: "id".to_string(),
+                        primary_key: true,
+                        unique: false,
+                        null: None,
+                        ..Default::default()
+                    },
+                    kind: Field::SmallSerial
+                },
+                TableField {
+                    options: CommonFieldOptions {
+                        name: "hits".to_string(),
+                        primary_key: false,
+                        unique: false,
+                        null: Some(false),
+                        ..Default::default()
+                    },
+                    kind: Field::Integer
+                },
+                TableField {
+                    options: CommonFieldOptions {
+                        name: "rank".to_string(),
+                        primary_key: false,
+                        unique: false,
+                        null: None,
+                        ..Default::default()
+                    },
+                    kind: Field::SmallInt
+                },
+            ],
+        };
+        let (observed, _) = IntoSql::<Pg>::into_sql_str(&counters).unwrap();
+        let expected = "CREATE TABLE counters (\n\tid SMALLSERIAL PRIMARY KEY,\n\thits INTEGER NOT NULL,\n\trank SMALLINT\n)";
         assert_eq!(observed, expected);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn numeric_field() {
+        let price = TableField {
+            options: CommonFieldOptions {
+                name: "price".to_string(),
+                primary_key: false,
+                unique: false,
+                null: Some(false),
+                ..Default::default()
+            },
+            kind: Field::Numeric { precision: 10, scale: Some(2) }
+        };
+        let (observed, _) = IntoSql::<Pg>::into_sql_str(&price).unwrap();
+        assert_eq!(observed, "price NUMERIC(10, 2) NOT NULL");
+
+        let (observed, _) = IntoSql::<Pg>::into_sql_str(&Field::Numeric { precision: 10, scale: Some(0) }).unwrap();
+        assert_eq!(observed, "NUMERIC(10, 0)");
+
+        let (observed, _) = IntoSql::<Pg>::into_sql_str(&Field::Numeric { precision: 7, scale: None }).unwrap();
+        assert_eq!(observed, "NUMERIC(7)");
+    }
+
+    #[test]
+    fn floating_point_fields() {
+        let (observed, _) = IntoSql::<Pg>::into_sql_str(&Field::Real).unwrap();
+        assert_eq!(observed, "REAL");
+
+        let (observed, _) = IntoSql::<Pg>::into_sql_str(&Field::DoublePrecision).unwrap();
+        assert_eq!(observed, "DOUBLE PRECISION");
+    }
+
+    #[test]
+    fn date_time_precision() {
+        let (observed, _) = IntoSql::<Pg>::into_sql_str(&Field::Timestamp { with_timezone: true, precision: Some(3) }).unwrap();
+        assert_eq!(observed, "TIMESTAMP(3) WITH TIME ZONE");
+
+        let (observed, _) = IntoSql::<Pg>::into_sql_str(&Field::Timestamp { with_timezone: false, precision: Some(0) }).unwrap();
+        assert_eq!(observed, "TIMESTAMP(0)");
+
+        let (observed, _) = IntoSql::<Pg>::into_sql_str(&Field::Time { with_timezone: false, precision: Some(6) }).unwrap();
+        assert_eq!(observed, "TIME(6)");
+
+        for field in [
+            Field::Timestamp { with_timezone: true, precision: Some(7) },
+            Field::Time { with_timezone: false, precision: Some(12) },
+        ] {
+            assert!(matches!(IntoSql::<Pg>::into_sql_str(&field), Err(Error::InvalidSchema(_))));
+        }
+    }
+
+    #[test]
+    fn interval_fields() {
+        for (field, expected) in [
+            (Field::Interval { fields: None, precision: None }, "INTERVAL"),
+            (Field::Interval { fields: Some("YEAR TO MONTH".to_string()), precision: None }, "INTERVAL YEAR TO MONTH"),
+            (Field::Interval { fields: None, precision: Some(3) }, "INTERVAL(3)"),
+            (Field::Interval { fields: Some("DAY TO SECOND".to_string()), precision: Some(3) }, "INTERVAL DAY TO SECOND(3)"),
+        ] {
+            let (observed, _) = IntoSql::<Pg>::into_sql_str(&field).unwrap();
+            assert_eq!(observed, expected);
+        }
+
+        let too_precise = Field::Interval { fields: None, precision: Some(9) };
+        assert!(matches!(IntoSql::<Pg>::into_sql_str(&too_precise), Err(Error::InvalidSchema(_))));
+    }
+
+    #[test]
+    fn date_time_fields() {
+        let (observed, _) = IntoSql::<Pg>::into_sql_str(&Field::Date).unwrap();
+        assert_eq!(observed, "DATE");
+
+        let (observed, _) = IntoSql::<Pg>::into_sql_str(&Field::Time { with_timezone: false, precision: None }).unwrap();
+        assert_eq!(observed, "TIME");
+
+        let (observed, _) = IntoSql::<Pg>::into_sql_str(&Field::Time { with_timezone: true, precision: None }).unwrap();
+        assert_eq!(observed, "TIME WITH TIME ZONE");
+
+        let (observed, _) = IntoSql::<Pg>::into_sql_str(&Field::Timestamp { with_timezone: false, precision: None }).unwrap();
+        assert_eq!(observed, "TIMESTAMP");
+
+        let (observed, _) = IntoSql::<Pg>::into_sql_str(&Field::Timestamp { with_timezone: true, precision: None }).unwrap();
+        assert_eq!(observed, "TIMESTAMP WITH TIME ZONE");
+    }
+
+    #[test]
+    fn uuid_field() {
+        let id = TableField {
+            options: CommonFieldOptions {
+                name: "id".to_string(),
+                primary_key: true,
+                unique: false,
+                null: None,
+                ..Default::default()
+            },
+            kind: Field::Uuid
+        };
+        let (observed, _) = IntoSql::<Pg>::into_sql_str(&id).unwrap();
+        assert_eq!(observed, "id UUID PRIMARY KEY");
+    }
+
+    #[test]
+    fn json_fields() {
+        let (observed, _) = IntoSql::<Pg>::into_sql_str(&Field::Json).unwrap();
+        assert_eq!(observed, "JSON");
+
+        let (observed, _) = IntoSql::<Pg>::into_sql_str(&Field::Jsonb).unwrap();
+        assert_eq!(observed, "JSONB");
+    }
+
+    #[test]
+    fn bytea_field() {
+        let checksum = TableField {
+            options: CommonFieldOptions {
+                name: "checksum".to_string(),
+                primary_key: false,
+                unique: true,
+                null: Some(false),
+                ..Default::default()
+            },
+            kind: Field::Bytea
+        };
+        let (observed, _) = IntoSql::<Pg>::into_sql_str(&checksum).unwrap();
+        assert_eq!(observed, "checksum BYTEA NOT NULL UNIQUE");
+    }
+
+    #[test]
+    fn array_field() {
+        let (observed, _) = IntoSql::<Pg>::into_sql_str(&Field::Array(Box::new(Field::Text))).unwrap();
+        assert_eq!(observed, "TEXT[]");
+    }
+
+    #[test]
+    fn nested_array_field() {
+        let matrix = Field::Array(Box::new(Field::Array(Box::new(Field::Integer))));
+        let (observed, _) = IntoSql::<Pg>::into_sql_str(&matrix).unwrap();
+        assert_eq!(observed, "INTEGER[][]");
+    }
+
+    #[test]
+    fn inet_field() {
+        let (observed, _) = IntoSql::<Pg>::into_sql_str(&Field::Inet).unwrap();
+        assert_eq!(observed, "INET");
+    }
+
+    #[test]
+    fn cidr_field() {
+        let (observed, _) = IntoSql::<Pg>::into_sql_str(&Field::Cidr).unwrap();
+        assert_eq!(observed, "CIDR");
+    }
+
+    #[test]
+    fn macaddr_field() {
+        let (observed, _) = IntoSql::<Pg>::into_sql_str(&Field::MacAddr).unwrap();
+        assert_eq!(observed, "MACADDR");
+    }
+
+    #[test]
+    fn varchar_field() {
+        let (observed, _) = IntoSql::<Pg>::into_sql_str(&Field::VarChar { max_length: Some(255) }).unwrap();
+        assert_eq!(observed, "VARCHAR(255)");
+
+        let (observed, _) = IntoSql::<Pg>::into_sql_str(&Field::VarChar { max_length: None }).unwrap();
+        assert_eq!(observed, "VARCHAR");
+    }
+
+    #[test]
+    fn money_field() {
+        let balance = TableField {
+            options: CommonFieldOptions {
+                name: "balance".to_string(),
+                primary_key: false,
+                unique: false,
+                null: Some(false),
+                ..Default::default()
+            },
+            kind: Field::Money
+        };
+        let (observed, _) = IntoSql::<Pg>::into_sql_str(&balance).unwrap();
+        assert_eq!(observed, "balance MONEY NOT NULL");
+    }
+
+    #[test]
+    fn table_without_fields() {
+        let empty = TableDefn {
+            options: CommonTableOptions { name: "post_tags".to_string(), if_not_exists: false, kind: None, ..Default::default() },
+            fields: vec![],
+        };
+        let mut writer = Vec::new();
+        assert!(matches!(IntoSql::<Pg>::into_sql(&empty, &mut writer), Err(Error::InvalidSchema(_))));
+        assert!(writer.is_empty());
+    }
+
+    #[test]
+    fn display() {
+        let title = TableField {
+            options: CommonFieldOptions {
+                name: "title".to_string(),
+                primary_key: false,
+                unique: false,
+                null: Some(false),
+                ..Default::default()
+            },
+            kind: Field::VarChar { max_length: Some(80) }
+        };
+        assert_eq!(title.kind.to_string(), "VARCHAR(80)");
+        assert_eq!(title.to_string(), "title VARCHAR(80) NOT NULL");
+
+        let posts = TableDefn {
+            options: CommonTableOptions { name: "posts".to_string(), if_not_exists: false, kind: None, ..Default::default() },
+            fields: vec![title],
+        };
+        let (expected, _) = IntoSql::<Pg>::into_sql_str(&posts).unwrap();
+        assert_eq!(format!("{posts}"), expected);
+    }
+
+    #[test]
+    fn composite_primary_key() {
+        let post_tags = TableDefn {
+            options: CommonTableOptions {
+                name: "post_tags".to_string(),
+                primary_key: vec!["post_id".to_string(), "tag_id".to_string()],
+                ..Default::default()
+            },
+            fields: vec![
+                TableField {
+                    options: CommonFieldOptions {
+                        name: "post_id".to_string(),
+                        primary_key: true,
+                        unique: false,
+                        null: Some(false),
+                        ..Default::default()
+                    },
+                    kind: Field::Integer
+                },
+                TableField {
+                    options: CommonFieldOptions {
+                        name: "tag_id".to_string(),
+                        primary_key: true,
+                        unique: false,
+                        null: Some(false),
+                        ..Default::default()
+                    },
+                    kind: Field::Integer
+                },
+            ],
+        };
+        let (observed, _) = IntoSql::<Pg>::into_sql_str(&post_tags).unwrap();
+        let expected = "CREATE TABLE post_tags (\n\tpost_id INTEGER NOT NULL,\n\ttag_id INTEGER NOT NULL,\n\tPRIMARY KEY (post_id, tag_id)\n)";
+        assert_eq!(observed, expected);
+    }
+
+    #[test]
+    fn literal_default() {
+        let views = TableField {
+            options: CommonFieldOptions {
+                name: "views".to_string(),
+                null: Some(false),
+                default: Some("0".to_string()),
+                ..Default::default()
+            },
+            kind: Field::Integer
+        };
+        let (observed, _) = IntoSql::<Pg>::into_sql_str(&views).unwrap();
+        assert_eq!(observed, "views INTEGER DEFAULT 0 NOT NULL");
+
+        let metadata = TableField {
+            options: CommonFieldOptions {
+                name: "metadata".to_string(),
+                null: Some(false),
+                default: Some("'{}'::jsonb".to_string()),
+                ..Default::default()
+            },
+            kind: Field::Jsonb
+        };
+        let (observed, _) = IntoSql::<Pg>::into_sql_str(&metadata).unwrap();
+        assert_eq!(observed, "metadata JSONB DEFAULT '{}'::jsonb NOT NULL");
+    }
+
+    #[test]
+    fn function_call_default() {
+        let created_at = TableField {
+            options: CommonFieldOptions {
+                name: "created_at".to_string(),
+                null: Some(false),
+                default: Some("now()".to_string()),
+                ..Default::default()
+            },
+            kind: Field::Timestamp { with_timezone: true, precision: None }
+        };
+        let (observed, _) = IntoSql::<Pg>::into_sql_str(&created_at).unwrap();
+        assert_eq!(observed, "created_at TIMESTAMP WITH TIME ZONE DEFAULT now() NOT NULL");
+
+        let id = TableField {
+            options: CommonFieldOptions {
+                name: "id".to_string(),
+                primary_key: true,
+                default: Some("gen_random_uuid()".to_string()),
+                ..Default::default()
+            },
+            kind: Field::Uuid
+        };
+        let (observed, _) = IntoSql::<Pg>::into_sql_str(&id).unwrap();
+        assert_eq!(observed, "id UUID DEFAULT gen_random_uuid() PRIMARY KEY");
+    }
+
+    #[test]
+    fn column_check() {
+        let price = TableField {
+            options: CommonFieldOptions {
+                name: "price".to_string(),
+                null: Some(false),
+                check: Some("price > 0".to_string()),
+                ..Default::default()
+            },
+            kind: Field::Numeric { precision: 10, scale: Some(2) }
+        };
+        let (observed, _) = IntoSql::<Pg>::into_sql_str(&price).unwrap();
+        assert_eq!(observed, "price NUMERIC(10, 2) NOT NULL CHECK (price > 0)");
+    }
+
+    #[test]
+    fn table_checks() {
+        let events = TableDefn {
+            options: CommonTableOptions {
+                name: "events".to_string(),
+                checks: vec![
+                    (Some("events_ends_after_start".to_string()), "ends_at > starts_at".to_string()),
+                    (None, "starts_at IS NOT NULL".to_string()),
+                ],
+                ..Default::default()
+            },
+            fields: vec![
+                TableField {
+                    options: CommonFieldOptions {
+                        name: "starts_at".to_string(),
+                        ..Default::default()
+                    },
+                    kind: Field::Timestamp { with_timezone: false, precision: None }
+                },
+                TableField {
+                    options: CommonFieldOptions {
+                        name: "ends_at".to_string(),
+                        ..Default::default()
+                    },
+                    kind: Field::Timestamp { with_timezone: false, precision: None }
+                },
+            ],
+        };
+        let (observed, _) = IntoSql::<Pg>::into_sql_str(&events).unwrap();
+        let expected = "CREATE TABLE events (\n\tstarts_at TIMESTAMP,\n\tends_at TIMESTAMP,\n\tCONSTRAINT events_ends_after_start CHECK (ends_at > starts_at),\n\tCHECK (starts_at IS NOT NULL)\n)";
+        assert_eq!(observed, expected);
+    }
+
+    #[test]
+    fn collation() {
+        let email = TableField {
+            options: CommonFieldOptions {
+                name: "email".to_string(),
+                unique: true,
+                null: Some(false),
+                default: Some("''".to_string()),
+                collation: Some("C".to_string()),
+                ..Default::default()
+            },
+            kind: Field::Text
+        };
+        let (observed, _) = IntoSql::<Pg>::into_sql_str(&email).unwrap();
+        assert_eq!(observed, "email TEXT COLLATE \"C\" DEFAULT '' NOT NULL UNIQUE");
+    }
+
+    #[test]
+    fn identity_always() {
+        let id = TableField {
+            options: CommonFieldOptions {
+                name: "id".to_string(),
+                primary_key: true,
+                identity: Some(IdentityKind::Always),
+                ..Default::default()
+            },
+            kind: Field::BigInt
+        };
+        let (observed, _) = IntoSql::<Pg>::into_sql_str(&id).unwrap();
+        assert_eq!(observed, "id BIGINT GENERATED ALWAYS AS IDENTITY PRIMARY KEY");
+    }
+
+    #[test]
+    fn identity_by_default() {
+        let id = TableField {
+            options: CommonFieldOptions {
+                name: "id".to_string(),
+                null: Some(false),
+                identity: Some(IdentityKind::ByDefault),
+                ..Default::default()
+            },
+            kind: Field::Integer
+        };
+        let (observed, _) = IntoSql::<Pg>::into_sql_str(&id).unwrap();
+        assert_eq!(observed, "id INTEGER GENERATED BY DEFAULT AS IDENTITY NOT NULL");
+    }
+
+    #[test]
+    fn stored_generated_column() {
+        let total = TableField {
+            options: CommonFieldOptions {
+                name: "total".to_string(),
+                generated: Some(GeneratedColumn { expression: "qty * price".to_string(), stored: true }),
+                ..Default::default()
+            },
+            kind: Field::Numeric { precision: 12, scale: Some(2) }
+        };
+        let (observed, _) = IntoSql::<Pg>::into_sql_str(&total).unwrap();
+        assert_eq!(observed, "total NUMERIC(12, 2) GENERATED ALWAYS AS (qty * price) STORED");
+    }
+
+    #[test]
+    fn generated_column_with_default() {
+        let total = TableField {
+            options: CommonFieldOptions {
+                name: "total".to_string(),
+                default: Some("0".to_string()),
+                generated: Some(GeneratedColumn { expression: "qty * price".to_string(), stored: true }),
+                ..Default::default()
+            },
+            kind: Field::Numeric { precision: 12, scale: Some(2) }
+        };
+        assert!(matches!(IntoSql::<Pg>::into_sql_str(&total), Err(Error::InvalidSchema(_))));
+    }
+
+    #[test]
+    fn virtual_generated_column() {
+        let total = TableField {
+            options: CommonFieldOptions {
+                name: "total".to_string(),
+                generated: Some(GeneratedColumn { expression: "qty * price".to_string(), stored: false }),
+                ..Default::default()
+            },
+            kind: Field::Numeric { precision: 12, scale: Some(2) }
+        };
+        assert!(matches!(IntoSql::<Pg>::into_sql_str(&total), Err(Error::InvalidSchema(_))));
+    }
+
+    #[test]
+    fn row_level_security() {
+        let documents = TableDefn::builder("documents")
+            .row_level_security()
+            .force_rls()
+            .column("tenant_id", Field::Integer, |c| c.not_null())
+            .build();
+
+        let mut writer = Vec::new();
+        let bytes_written = documents.into_sql_with_alters(&mut writer, &FormatOptions::default()).unwrap();
+        let observed = String::from_utf8(writer).unwrap();
+        let expected = concat!(
+            "CREATE TABLE documents (\n\ttenant_id INTEGER NOT NULL\n);\n",
+            "ALTER TABLE documents ENABLE ROW LEVEL SECURITY;\n",
+            "ALTER TABLE documents FORCE ROW LEVEL SECURITY;",
+        );
+        assert_eq!(observed, expected);
+        assert_eq!(bytes_written, expected.len());
+    }
+
+    #[test]
+    fn owner_statement() {
+        let documents = TableDefn::builder("documents")
+            .quote_identifiers()
+            .owner("App Owner")
+            .column("id", Field::Integer, |c| c)
+            .build();
+
+        let mut writer = Vec::new();
+        documents.into_sql_with_alters(&mut writer, &FormatOptions::default()).unwrap();
+        let observed = String::from_utf8(writer).unwrap();
+        let expected = "CREATE TABLE documents (\n\tid INTEGER\n);\nALTER TABLE documents OWNER TO \"App Owner\";";
+        assert_eq!(observed, expected);
+    }
+
+    #[test]
+    fn table_with_comments() {
+        let posts = TableDefn {
+            options: CommonTableOptions {
+                name: "posts".to_string(),
+                comment: Some("Everything that's been published".to_string()),
+                ..Default::default()
+            },
+            fields: vec![
+                TableField {
+                    options: CommonFieldOptions {
+                        name: "id".to_string(),
+                        primary_key: true,
+                        ..Default::default()
+                    },
+                    kind: Field::Serial
+                },
+                TableField {
+                    options: CommonFieldOptions {
+                        name: "title".to_string(),
+                        comment: Some("The post's headline".to_string()),
+                        ..Default::default()
+                    },
+                    kind: Field::Text
+                },
+            ],
+        };
+        let mut writer = Vec::new();
+        let bytes_written = posts.into_sql_with_comments(&mut writer, &FormatOptions::default()).unwrap();
+        let observed = String::from_utf8(writer).unwrap();
+        let expected = concat!(
+            "CREATE TABLE posts (\n\tid SERIAL PRIMARY KEY,\n\ttitle TEXT\n);\n",
+            "COMMENT ON TABLE posts IS 'Everything that''s been published';\n",
+            "COMMENT ON COLUMN posts.title IS 'The post''s headline';",
+        );
+        assert_eq!(observed, expected);
+        assert_eq!(bytes_written, expected.len());
+    }
+
+    #[test]
+    fn multi_line_comments() {
+        let posts = TableDefn::builder("posts")
+            .comment("Published posts.\nDrafts live elsewhere.")
+            .column("title", Field::Text, |c| c.comment("The post's headline,\nshown in feeds."))
+            .build();
+        let mut writer = Vec::new();
+        posts.into_sql_with_comments(&mut writer, &FormatOptions::default()).unwrap();
+        let observed = String::from_utf8(writer).unwrap();
+        let expected = concat!(
+            "CREATE TABLE posts (\n\ttitle TEXT\n);\n",
+            "COMMENT ON TABLE posts IS 'Published posts.\nDrafts live elsewhere.';\n",
+            "COMMENT ON COLUMN posts.title IS 'The post''s headline,\nshown in feeds.';",
+        );
+        assert_eq!(observed, expected);
+    }
+
+    #[test]
+    fn temporary_table() {
+        let staging = TableDefn {
+            options: CommonTableOptions { name: "staging".to_string(), kind: Some(TableKind::Local), ..Default::default() },
+            fields: vec![
+                TableField {
+                    options: CommonFieldOptions {
+                        name: "line".to_string(),
+                        ..Default::default()
+                    },
+                    kind: Field::Text
+                },
+            ],
+        };
+        let (observed, _) = IntoSql::<Pg>::into_sql_str(&staging).unwrap();
+        assert_eq!(observed, "CREATE LOCAL TEMPORARY TABLE staging (\n\tline TEXT\n)");
+    }
+
+    #[test]
+    fn unlogged_table() {
+        let mut staging = TableDefn {
+            options: CommonTableOptions { name: "staging".to_string(), unlogged: true, ..Default::default() },
+            fields: vec![
+                TableField {
+                    options: CommonFieldOptions {
+                        name: "line".to_string(),
+                        ..Default::default()
+                    },
+                    kind: Field::Text
+                },
+            ],
+        };
+        let (observed, _) = IntoSql::<Pg>::into_sql_str(&staging).unwrap();
+        assert_eq!(observed, "CREATE UNLOGGED TABLE staging (\n\tline TEXT\n)");
+
+        staging.options.kind = Some(TableKind::Global);
+        assert!(matches!(IntoSql::<Pg>::into_sql_str(&staging), Err(Error::InvalidSchema(_))));
+    }
+
+    #[test]
+    fn table_kind() {
+        let (observed, _) = IntoSql::<Pg>::into_sql_str(&TableKind::Global).unwrap();
+        assert_eq!(observed, "GLOBAL");
+
+        let (observed, _) = IntoSql::<Pg>::into_sql_str(&TableKind::Local).unwrap();
+        assert_eq!(observed, "LOCAL");
+    }
+
+    #[test]
+    fn reserved_word_identifiers() {
+        let mut orders = TableDefn {
+            options: CommonTableOptions { name: "user".to_string(), ..Default::default() },
+            fields: vec![
+                TableField {
+                    options: CommonFieldOptions {
+                        name: "order".to_string(),
+                        null: Some(false),
+                        ..Default::default()
+                    },
+                    kind: Field::Integer
+                },
+                TableField {
+                    options: CommonFieldOptions {
+                        name: "note".to_string(),
+                        ..Default::default()
+                    },
+                    kind: Field::Text
+                },
+            ],
+        };
+        let (observed, _) = IntoSql::<Pg>::into_sql_str(&orders).unwrap();
+        assert_eq!(observed, "CREATE TABLE user (\n\torder INTEGER NOT NULL,\n\tnote TEXT\n)");
+
+        orders.options.quote_identifiers = true;
+        let (observed, _) = IntoSql::<Pg>::into_sql_str(&orders).unwrap();
+        assert_eq!(observed, "CREATE TABLE \"user\" (\n\t\"order\" INTEGER NOT NULL,\n\tnote TEXT\n)");
+    }
+
+    #[test]
+    fn zero_length_fields_are_rejected() {
+        for field in [
+            Field::Char { max_length: Some(0) },
+            Field::VarChar { max_length: Some(0) },
+            Field::Bit { length: 0 },
+            Field::BitVarying { length: Some(0) },
+            Field::Array(Box::new(Field::Char { max_length: Some(0) })),
+        ] {
+            assert!(matches!(IntoSql::<Pg>::into_sql_str(&field), Err(Error::InvalidSchema(_))));
+        }
+    }
+
+    #[test]
+    fn positive_length_fields_are_accepted() {
+        assert!(Field::Char { max_length: Some(1) }.validate().is_ok());
+        assert!(Field::VarChar { max_length: None }.validate().is_ok());
+        let (observed, _) = IntoSql::<Pg>::into_sql_str(&Field::Bit { length: 8 }).unwrap();
+        assert_eq!(observed, "BIT(8)");
+    }
+
+    #[test]
+    fn schema_qualified_table() {
+        let mut users = TableDefn {
+            options: CommonTableOptions { name: "users".to_string(), schema: Some("auth".to_string()), ..Default::default() },
+            fields: vec![
+                TableField {
+                    options: CommonFieldOptions { name: "id".to_string(), primary_key: true, ..Default::default() },
+                    kind: Field::Serial
+                },
+            ],
+        };
+        let (observed, _) = IntoSql::<Pg>::into_sql_str(&users).unwrap();
+        assert_eq!(observed, "CREATE TABLE auth.users (\n\tid SERIAL PRIMARY KEY\n)");
+
+        users.options.schema = Some("Auth".to_string());
+        users.options.name = "user".to_string();
+        users.options.quote_identifiers = true;
+        let (observed, _) = IntoSql::<Pg>::into_sql_str(&users).unwrap();
+        assert_eq!(observed, "CREATE TABLE \"Auth\".\"user\" (\n\tid SERIAL PRIMARY KEY\n)");
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip() {
+        let posts = TableDefn {
+            options: CommonTableOptions { name: "posts".to_string(), if_not_exists: true, kind: Some(TableKind::Global), ..Default::default() },
+            fields: vec![
+                TableField {
+                    options: CommonFieldOptions { name: "id".to_string(), primary_key: true, ..Default::default() },
+                    kind: Field::Serial
+                },
+                TableField {
+                    options: CommonFieldOptions { name: "title".to_string(), null: Some(false), ..Default::default() },
+                    kind: Field::Char { max_length: Some(10) }
+                },
+                TableField {
+                    options: CommonFieldOptions { name: "tags".to_string(), ..Default::default() },
+                    kind: Field::Array(Box::new(Field::Text))
+                },
+            ],
+        };
+        let json = serde_json::to_string(&posts).unwrap();
+        let restored: TableDefn = serde_json::from_str(&json).unwrap();
+        assert_eq!(serde_json::to_string(&restored).unwrap(), json);
+        assert_eq!(restored.fields[1].kind, posts.fields[1].kind);
+        assert_eq!(
+            IntoSql::<Pg>::into_sql_str(&restored).unwrap().0,
+            IntoSql::<Pg>::into_sql_str(&posts).unwrap().0,
+        );
+    }
+
+    #[test]
+    fn io_and_fmt_paths_agree() {
+        let posts = TableDefn {
+            options: CommonTableOptions { name: "posts".to_string(), primary_key: vec!["id".to_string()], ..Default::default() },
+            fields: vec![
+                TableField {
+                    options: CommonFieldOptions { name: "id".to_string(), ..Default::default() },
+                    kind: Field::Array(Box::new(Field::Numeric { precision: 10, scale: Some(2) }))
+                },
+                TableField {
+                    options: CommonFieldOptions { name: "title".to_string(), null: Some(false), default: Some("'untitled'".to_string()), ..Default::default() },
+                    kind: Field::VarChar { max_length: Some(80) }
+                },
+            ],
+        };
+
+        let mut bytes = Vec::new();
+        let io_bytes = IntoSql::<Pg>::into_sql(&posts, &mut bytes).unwrap();
+        let mut sql = String::new();
+        let fmt_bytes = IntoSql::<Pg>::into_sql_fmt(&posts, &mut sql).unwrap();
+
+        assert_eq!(String::from_utf8(bytes).unwrap(), sql);
+        assert_eq!(io_bytes, fmt_bytes);
+        assert_eq!(IntoSql::<Pg>::into_sql_str(&posts).unwrap().0, sql);
+    }
+
+    #[test]
+    fn io_errors_are_surfaced() {
+        struct Broken;
+
+        impl io::Write for Broken {
+            fn write(&mut self, _: &[u8]) -> io::Result<usize> {
+                Err(io::Error::other("connection reset"))
+            }
+
+            fn flush(&mut self) -> io::Result<()> {
+                Ok(())
+            }
+        }
+
+        assert!(matches!(IntoSql::<Pg>::into_sql(&Field::Text, &mut Broken), Err(Error::Io(_))));
+    }
+
+    #[test]
+    fn parameterized_types_report_their_length() {
+        for (field, expected) in [
+            (Field::Char { max_length: Some(10) }, "CHAR(10)"),
+            (Field::VarChar { max_length: Some(255) }, "VARCHAR(255)"),
+            (Field::Numeric { precision: 10, scale: Some(2) }, "NUMERIC(10, 2)"),
+            (Field::Numeric { precision: 8, scale: None }, "NUMERIC(8)"),
+            (Field::Bit { length: 64 }, "BIT(64)"),
+            (Field::BitVarying { length: Some(64) }, "BIT VARYING(64)"),
+            (Field::BitVarying { length: None }, "BIT VARYING"),
+            (Field::Text, "TEXT"),
+        ] {
+            let (observed, bytes_written) = IntoSql::<Pg>::into_sql_str(&field).unwrap();
+            assert_eq!(observed, expected);
+            assert_eq!(bytes_written, expected.len());
+        }
+    }
+
+    #[test]
+    fn byte_count_matches_output() {
+        let posts = TableDefn {
+            options: CommonTableOptions {
+                name: "posts".to_string(),
+                kind: Some(TableKind::Local),
+                primary_key: vec!["id".to_string(), "slug".to_string()],
+                checks: vec![(Some("title_not_blank".to_string()), "title <> ''".to_string())],
+                ..Default::default()
+            },
+            fields: vec![
+                TableField {
+                    options: CommonFieldOptions { name: "id".to_string(), ..Default::default() },
+                    kind: Field::BigSerial
+                },
+                TableField {
+                    options: CommonFieldOptions { name: "slug".to_string(), unique: true, ..Default::default() },
+                    kind: Field::VarChar { max_length: Some(64) }
+                },
+                TableField {
+                    options: CommonFieldOptions { name: "title".to_string(), null: Some(false), collation: Some("C".to_string()), ..Default::default() },
+                    kind: Field::Text
+                },
+            ],
+        };
+        let (observed, bytes_written) = IntoSql::<Pg>::into_sql_str(&posts).unwrap();
+        assert_eq!(bytes_written, observed.len());
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn async_matches_sync() {
+        let posts = TableDefn {
+            options: CommonTableOptions { name: "posts".to_string(), if_not_exists: true, ..Default::default() },
+            fields: vec![
+                TableField {
+                    options: CommonFieldOptions { name: "id".to_string(), primary_key: true, ..Default::default() },
+                    kind: Field::Serial
+                },
+                TableField {
+                    options: CommonFieldOptions { name: "body".to_string(), null: Some(false), ..Default::default() },
+                    kind: Field::Text
+                },
+            ],
+        };
+        let mut buffer = Vec::new();
+        let bytes_written = IntoSql::<Pg>::into_sql_async(&posts, &mut buffer).await.unwrap();
+        let (expected, _) = IntoSql::<Pg>::into_sql_str(&posts).unwrap();
+        assert_eq!(String::from_utf8(buffer).unwrap(), expected);
+        assert_eq!(bytes_written, expected.len());
+    }
+
+    #[test]
+    fn inherits() {
+        let mut measurements = TableDefn {
+            options: CommonTableOptions { name: "measurements_2023".to_string(), inherits: vec!["measurements".to_string()], ..Default::default() },
+            fields: vec![
+                TableField {
+                    options: CommonFieldOptions { name: "reading".to_string(), ..Default::default() },
+                    kind: Field::Real
+                },
+            ],
+        };
+        let (observed, _) = IntoSql::<Pg>::into_sql_str(&measurements).unwrap();
+        assert_eq!(observed, "CREATE TABLE measurements_2023 (\n\treading REAL\n) INHERITS (measurements)");
+
+        measurements.options.inherits.push("audited".to_string());
+        let (observed, _) = IntoSql::<Pg>::into_sql_str(&measurements).unwrap();
+        assert_eq!(observed, "CREATE TABLE measurements_2023 (\n\treading REAL\n) INHERITS (measurements, audited)");
+    }
+
+    #[test]
+    fn storage_params() {
+        let posts = TableDefn {
+            options: CommonTableOptions {
+                name: "posts".to_string(),
+                storage_params: vec![
+                    ("fillfactor".to_string(), "70".to_string()),
+                    ("autovacuum_enabled".to_string(), "false".to_string()),
+                ],
+                ..Default::default()
+            },
+            fields: vec![
+                TableField {
+                    options: CommonFieldOptions { name: "id".to_string(), primary_key: true, ..Default::default() },
+                    kind: Field::Serial
+                },
+            ],
+        };
+        let (observed, _) = IntoSql::<Pg>::into_sql_str(&posts).unwrap();
+        assert_eq!(observed, "CREATE TABLE posts (\n\tid SERIAL PRIMARY KEY\n) WITH (fillfactor = 70, autovacuum_enabled = false)");
+    }
+
+    #[test]
+    fn tablespace_follows_other_trailing_clauses() {
+        use crate::table::partition::{PartitionSpec, PartitionStrategy};
+
+        let mut events = TableDefn {
+            options: CommonTableOptions {
+                name: "events".to_string(),
+                partition_by: Some(PartitionSpec { strategy: PartitionStrategy::Range, keys: vec!["id".to_string()] }),
+                storage_params: vec![("fillfactor".to_string(), "70".to_string())],
+                tablespace: Some("fast_ssd".to_string()),
+                ..Default::default()
+            },
+            fields: vec![
+                TableField {
+                    options: CommonFieldOptions { name: "id".to_string(), ..Default::default() },
+                    kind: Field::BigInt
+                },
+            ],
+        };
+        let (observed, _) = IntoSql::<Pg>::into_sql_str(&events).unwrap();
+        assert_eq!(observed, "CREATE TABLE events (\n\tid BIGINT\n) PARTITION BY RANGE (id) WITH (fillfactor = 70) TABLESPACE fast_ssd");
+
+        events.options.tablespace = Some("Fast SSD".to_string());
+        events.options.quote_identifiers = true;
+        let (observed, _) = IntoSql::<Pg>::into_sql_str(&events).unwrap();
+        assert!(observed.ends_with(" TABLESPACE \"Fast SSD\""));
+    }
+
+    #[test]
+    fn pretty_and_compact_layouts() {
+        let posts = TableDefn {
+            options: CommonTableOptions { name: "posts".to_string(), primary_key: vec!["id".to_string()], ..Default::default() },
+            fields: vec![
+                TableField {
+                    options: CommonFieldOptions { name: "id".to_string(), ..Default::default() },
+                    kind: Field::Serial
+                },
+                TableField {
+                    options: CommonFieldOptions { name: "title".to_string(), null: Some(false), ..Default::default() },
+                    kind: Field::Char { max_length: Some(10) }
+                },
+            ],
+        };
+
+        let mut pretty = String::new();
+        posts.into_sql_with_options(&mut pretty, &FormatOptions::default()).unwrap();
+        assert_eq!(pretty, "CREATE TABLE posts (\n\tid SERIAL,\n\ttitle CHAR(10) NOT NULL,\n\tPRIMARY KEY (id)\n)");
+        assert_eq!(pretty, IntoSql::<Pg>::into_sql_str(&posts).unwrap().0);
+
+        let mut compact = String::new();
+        let bytes_written = posts.into_sql_with_options(&mut compact, &FormatOptions::compact()).unwrap();
+        assert_eq!(compact, "CREATE TABLE posts (id SERIAL, title CHAR(10) NOT NULL, PRIMARY KEY (id))");
+        assert_eq!(bytes_written, compact.len());
+    }
+
+    #[test]
+    fn named_inline_constraints() {
+        let users = TableDefn {
+            options: CommonTableOptions { name: "users".to_string(), ..Default::default() },
+            fields: vec![
+                TableField {
+                    options: CommonFieldOptions {
+                        name: "id".to_string(),
+                        primary_key: true,
+                        primary_key_constraint: Some("pk_users".to_string()),
+                        ..Default::default()
+                    },
+                    kind: Field::Serial
+                },
+                TableField {
+                    options: CommonFieldOptions {
+                        name: "email".to_string(),
+                        null: Some(false),
+                        unique: true,
+                        unique_constraint: Some("uq_email".to_string()),
+                        check: Some("email LIKE '%@%'".to_string()),
+                        check_constraint: Some("ck_email".to_string()),
+                        ..Default::default()
+                    },
+                    kind: Field::Text
+                },
+            ],
+        };
+        let (observed, _) = IntoSql::<Pg>::into_sql_str(&users).unwrap();
+        let expected = "CREATE TABLE users (\n\tid SERIAL CONSTRAINT pk_users PRIMARY KEY,\n\temail TEXT NOT NULL CONSTRAINT uq_email UNIQUE CONSTRAINT ck_email CHECK (email LIKE '%@%')\n)";
+        assert_eq!(observed, expected);
+    }
+
+    #[test]
+    fn multi_column_unique_constraints() {
+        let mut memberships = TableDefn {
+            options: CommonTableOptions {
+                name: "memberships".to_string(),
+                unique_constraints: vec![(None, vec!["team_id".to_string(), "user_id".to_string()])],
+                ..Default::default()
+            },
+            fields: vec![
+                TableField {
+                    options: CommonFieldOptions { name: "team_id".to_string(), ..Default::default() },
+                    kind: Field::Integer
+                },
+                TableField {
+                    options: CommonFieldOptions { name: "user_id".to_string(), ..Default::default() },
+                    kind: Field::Integer
+                },
+            ],
+        };
+        let (observed, _) = IntoSql::<Pg>::into_sql_str(&memberships).unwrap();
+        assert_eq!(observed, "CREATE TABLE memberships (\n\tteam_id INTEGER,\n\tuser_id INTEGER,\n\tUNIQUE (team_id, user_id)\n)");
+
+        memberships.options.unique_constraints[0].0 = Some("uq_team_user".to_string());
+        let (observed, _) = IntoSql::<Pg>::into_sql_str(&memberships).unwrap();
+        assert_eq!(observed, "CREATE TABLE memberships (\n\tteam_id INTEGER,\n\tuser_id INTEGER,\n\tCONSTRAINT uq_team_user UNIQUE (team_id, user_id)\n)");
+    }
+
+    #[test]
+    fn execute_against_sqlite() {
+        use diesel::sqlite::SqliteConnection;
+
+        let mut conn = SqliteConnection::establish(":memory:").unwrap();
+        let posts = TableDefn::builder("posts")
+            .column("id", Field::Serial, |c| c.primary_key())
+            .column("title", Field::Text, |c| c.not_null())
+            .build();
+        assert_eq!(posts.execute(&mut conn).unwrap(), 0);
+        assert!(posts.execute(&mut conn).is_err());
+    }
+
+    #[test]
+    fn lowercase_keywords() {
+        use crate::table::constraints::{ForeignKeyRef, ReferentialAction};
+        use crate::table::format::KeywordCase;
+
+        let posts = TableDefn::builder("Posts")
+            .if_not_exists()
+            .kind(TableKind::Global)
+            .column("ID", Field::Serial, |c| c.primary_key())
+            .column("title", Field::VarChar { max_length: Some(10) }, |c| c.not_null().default_value("'Untitled'"))
+            .column("mood", Field::Enum { type_name: "Mood".to_string(), schema: None }, |c| c)
+            .column("author_id", Field::Integer, |c| {
+                c.references = Some(ForeignKeyRef {
+                    table: "Users".to_string(),
+                    column: "ID".to_string(),
+                    on_delete: Some(ReferentialAction::SetNull),
+                    ..Default::default()
+                });
+                c
+            })
+            .build();
+        let format = FormatOptions { keyword_case: KeywordCase::Lower, ..Default::default() };
+        let mut observed = String::new();
+        posts.into_sql_with_options(&mut observed, &format).unwrap();
+        let expected = "create global temporary table if not exists Posts (\n\tID serial primary key,\n\ttitle varchar(10) default 'Untitled' not null,\n\tmood Mood,\n\tauthor_id integer references Users(ID) on delete set null\n)";
+        assert_eq!(observed, expected);
+    }
+
+    #[test]
+    fn lowercase_keywords_keep_verbatim_type_parts() {
+        use crate::table::format::KeywordCase;
+
+        let events = TableDefn::builder("events")
+            .column("duration", Field::Interval { fields: Some("DAY TO SECOND".to_string()), precision: Some(3) }, |c| c)
+            .column("moods", Field::Array(Box::new(Field::Enum { type_name: "Mood".to_string(), schema: Some("App".to_string()) })), |c| c)
+            .column("code", Field::VarChar { max_length: Some(8) }, |c| c)
+            .build();
+        let format = FormatOptions { keyword_case: KeywordCase::Lower, ..Default::default() };
+        let mut observed = String::new();
+        events.into_sql_with_options(&mut observed, &format).unwrap();
+        let expected = "create table events (\n\tduration interval DAY TO SECOND(3),\n\tmoods App.Mood[],\n\tcode varchar(8)\n)";
+        assert_eq!(observed, expected);
+    }
+
+    #[test]
+    fn lowercase_comments_and_alters() {
+        use crate::table::format::KeywordCase;
+
+        let documents = TableDefn::builder("documents")
+            .row_level_security()
+            .owner("app")
+            .comment("Uploads")
+            .column("id", Field::Integer, |c| c)
+            .build();
+        let format = FormatOptions { keyword_case: KeywordCase::Lower, ..Default::default() };
+
+        let mut writer = Vec::new();
+        documents.into_sql_with_alters(&mut writer, &format).unwrap();
+        let expected = concat!(
+            "create table documents (\n\tid integer\n);\n",
+            "alter table documents enable row level security;\n",
+            "alter table documents owner to app;",
+        );
+        assert_eq!(String::from_utf8(writer).unwrap(), expected);
+
+        let mut writer = Vec::new();
+        documents.into_sql_with_comments(&mut writer, &format).unwrap();
+        let expected = "create table documents (\n\tid integer\n);\ncomment on table documents is 'Uploads';";
+        assert_eq!(String::from_utf8(writer).unwrap(), expected);
+    }
+
+    #[test]
+    fn char_spelling() {
+        use crate::table::format::CharSpelling;
+
+        let codes = TableDefn::builder("codes")
+            .column("flag", Field::Char { max_length: None }, |c| c)
+            .column("code", Field::Char { max_length: Some(10) }, |c| c)
+            .column("tags", Field::Array(Box::new(Field::Char { max_length: Some(2) })), |c| c)
+            .build();
+        let (observed, _) = IntoSql::<Pg>::into_sql_str(&codes).unwrap();
+        assert_eq!(observed, "CREATE TABLE codes (\n\tflag CHAR,\n\tcode CHAR(10),\n\ttags CHAR(2)[]\n)");
+        assert_eq!(TableDefn::parse(&observed).unwrap(), codes);
+
+        let format = FormatOptions { char_spelling: CharSpelling::Character, ..Default::default() };
+        let mut observed = String::new();
+        codes.into_sql_with_options(&mut observed, &format).unwrap();
+        assert_eq!(observed, "CREATE TABLE codes (\n\tflag CHARACTER,\n\tcode CHARACTER(10),\n\ttags CHARACTER(2)[]\n)");
+        assert_eq!(TableDefn::parse(&observed).unwrap(), codes);
+    }
+
+    #[test]
+    fn equal_tables_compare_equal() {
+        let posts = || TableDefn::builder("posts")
+            .column("id", Field::Serial, |c| c.primary_key())
+            .column("tags", Field::Array(Box::new(Field::Text)), |c| c.not_null().references("tags", "id"))
+            .build();
+        assert_eq!(posts(), posts());
+        assert_eq!(posts().clone(), posts());
+
+        let mut renamed = posts();
+        renamed.fields[1].options.name = "labels".to_string();
+        assert_ne!(renamed, posts());
+    }
+
+    #[test]
+    fn duplicate_columns_are_rejected() {
+        let posts = TableDefn::builder("posts")
+            .column("id", Field::Serial, |c| c.primary_key())
+            .column("title", Field::Text, |c| c)
+            .column("id", Field::BigInt, |c| c)
+            .build();
+        match posts.validate() {
+            Err(Error::InvalidSchema(reason)) => assert_eq!(reason, "table posts has duplicate columns: id"),
+            other => panic!("expected a duplicate column error, got {:?}", other),
+        }
+        assert!(matches!(IntoSql::<Pg>::into_sql_str(&posts), Err(Error::InvalidSchema(_))));
+    }
+}
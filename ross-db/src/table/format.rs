@@ -0,0 +1,120 @@
+use std::borrow::Cow;
+use std::fmt;
+
+use diesel::pg::Pg;
+
+use crate::Error;
+use crate::table::fields::{IntoSql, SqlWrite};
+
+
+/// How SQL keywords are spelled; identifiers and verbatim expressions are never touched.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum KeywordCase {
+    #[default]
+    Upper,
+    Lower,
+}
+
+
+/// How `CHAR` columns are spelled; the server treats `CHAR` and `CHARACTER` as the same type.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CharSpelling {
+    #[default]
+    Char,
+    Character,
+}
+
+impl CharSpelling {
+    pub(crate) fn keyword(self) -> &'static str {
+        match self {
+            Self::Char => "CHAR",
+            Self::Character => "CHARACTER",
+        }
+    }
+}
+
+
+/// Layout choices for multi-line statements such as `CREATE TABLE`.
+#[derive(Debug, Clone)]
+pub struct FormatOptions {
+    /// Put every column on its own indented line; otherwise everything goes on one line.
+    pub pretty: bool,
+    /// The indentation used for each line in pretty mode.
+    pub indent: String,
+    pub keyword_case: KeywordCase,
+    pub char_spelling: CharSpelling,
+}
+
+impl Default for FormatOptions {
+    fn default() -> Self {
+        Self {
+            pretty: true,
+            indent: "\t".to_string(),
+            keyword_case: KeywordCase::Upper,
+            char_spelling: CharSpelling::Char,
+        }
+    }
+}
+
+impl FormatOptions {
+    /// Everything on one line, columns separated by `, `.
+    pub fn compact() -> Self {
+        Self {
+            pretty: false,
+            ..Default::default()
+        }
+    }
+
+    /// Pretty output indented with `indent`, e.g. four spaces, instead of a tab.
+    pub fn with_indent(indent: impl Into<String>) -> Self {
+        Self {
+            indent: indent.into(),
+            ..Default::default()
+        }
+    }
+
+    pub(crate) fn cased<'a>(&self, keyword: &'a str) -> Cow<'a, str> {
+        match self.keyword_case {
+            KeywordCase::Upper => Cow::Borrowed(keyword),
+            KeywordCase::Lower => Cow::Owned(keyword.to_lowercase()),
+        }
+    }
+
+    pub(crate) fn keyword(&self, writer: &mut dyn fmt::Write, keyword: &str) -> Result<usize, Error> {
+        writer.write_sql(&self.cased(keyword))
+    }
+
+    pub(crate) fn keywords(&self, writer: &mut dyn fmt::Write, item: &dyn IntoSql<Pg>) -> Result<usize, Error> {
+        match self.keyword_case {
+            KeywordCase::Upper => item.into_sql_fmt(writer),
+            KeywordCase::Lower => {
+                let (sql, _) = item.into_sql_str()?;
+                writer.write_sql(&sql.to_lowercase())
+            },
+        }
+    }
+
+    pub(crate) fn open_list(&self, writer: &mut dyn fmt::Write) -> Result<usize, Error> {
+        if self.pretty {
+            Ok(writer.write_sql(" (\n")? + writer.write_sql(&self.indent)?)
+        } else {
+            writer.write_sql(" (")
+        }
+    }
+
+    pub(crate) fn list_separator(&self, writer: &mut dyn fmt::Write) -> Result<usize, Error> {
+        if self.pretty {
+            Ok(writer.write_sql(",\n")? + writer.write_sql(&self.indent)?)
+        } else {
+            writer.write_sql(", ")
+        }
+    }
+
+    pub(crate) fn close_list(&self, writer: &mut dyn fmt::Write) -> Result<usize, Error> {
+        if self.pretty {
+            writer.write_sql("\n)")
+        } else {
+            writer.write_sql(")")
+        }
+    }
+}
@@ -0,0 +1,151 @@
+use std::fmt;
+
+use diesel::pg::Pg;
+
+use crate::Error;
+use crate::table::fields::{IntoSql, SqlWrite};
+
+
+/// A table privilege that can be granted to or revoked from a role.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Privilege {
+    Select,
+    Insert,
+    Update,
+    Delete,
+    Truncate,
+    References,
+    Trigger,
+    All,
+}
+
+impl IntoSql<Pg> for Privilege {
+    fn into_sql_fmt(&self, writer: &mut dyn fmt::Write) -> Result<usize, Error> {
+        let privilege = match self {
+            Self::Select => "SELECT",
+            Self::Insert => "INSERT",
+            Self::Update => "UPDATE",
+            Self::Delete => "DELETE",
+            Self::Truncate => "TRUNCATE",
+            Self::References => "REFERENCES",
+            Self::Trigger => "TRIGGER",
+            Self::All => "ALL",
+        };
+        writer.write_sql(privilege)
+    }
+}
+
+fn write_privileges(writer: &mut dyn fmt::Write, privileges: &[Privilege]) -> Result<usize, Error> {
+    if privileges.is_empty() {
+        return Err(Error::InvalidSchema("at least one privilege is required".to_string()));
+    }
+
+    let mut total_bytes = 0;
+    for (index, privilege) in privileges.iter().enumerate() {
+        if index > 0 {
+            total_bytes += writer.write_sql(", ")?;
+        }
+        total_bytes += privilege.into_sql_fmt(writer)?;
+    }
+    Ok(total_bytes)
+}
+
+
+/// A `GRANT privileges ON table TO role` statement.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Grant {
+    pub privileges: Vec<Privilege>,
+    pub on_table: String,
+    pub to_role: String,
+    pub with_grant_option: bool,
+}
+
+impl IntoSql<Pg> for Grant {
+    fn into_sql_fmt(&self, writer: &mut dyn fmt::Write) -> Result<usize, Error> {
+        let mut total_bytes = 0;
+        total_bytes += writer.write_sql("GRANT ")?;
+        total_bytes += write_privileges(writer, &self.privileges)?;
+        total_bytes += writer.write_sql(" ON ")?;
+        total_bytes += writer.write_sql(&self.on_table)?;
+        total_bytes += writer.write_sql(" TO ")?;
+        total_bytes += writer.write_sql(&self.to_role)?;
+        if self.with_grant_option {
+            total_bytes += writer.write_sql(" WITH GRANT OPTION")?;
+        }
+        Ok(total_bytes)
+    }
+}
+
+
+/// A `REVOKE privileges ON table FROM role` statement.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Revoke {
+    pub privileges: Vec<Privilege>,
+    pub on_table: String,
+    pub from_role: String,
+}
+
+impl IntoSql<Pg> for Revoke {
+    fn into_sql_fmt(&self, writer: &mut dyn fmt::Write) -> Result<usize, Error> {
+        let mut total_bytes = 0;
+        total_bytes += writer.write_sql("REVOKE ")?;
+        total_bytes += write_privileges(writer, &self.privileges)?;
+        total_bytes += writer.write_sql(" ON ")?;
+        total_bytes += writer.write_sql(&self.on_table)?;
+        total_bytes += writer.write_sql(" FROM ")?;
+        total_bytes += writer.write_sql(&self.from_role)?;
+        Ok(total_bytes)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn single_privilege() {
+        let grant = Grant {
+            privileges: vec![Privilege::Select],
+            on_table: "posts".to_string(),
+            to_role: "reader".to_string(),
+            with_grant_option: false,
+        };
+        let (observed, _) = IntoSql::<Pg>::into_sql_str(&grant).unwrap();
+        assert_eq!(observed, "GRANT SELECT ON posts TO reader");
+
+        let revoke = Revoke {
+            privileges: vec![Privilege::Select, Privilege::Insert],
+            on_table: "posts".to_string(),
+            from_role: "reader".to_string(),
+        };
+        let (observed, _) = IntoSql::<Pg>::into_sql_str(&revoke).unwrap();
+        assert_eq!(observed, "REVOKE SELECT, INSERT ON posts FROM reader");
+    }
+
+    #[test]
+    fn all_privileges() {
+        let grant = Grant {
+            privileges: vec![Privilege::All],
+            on_table: "posts".to_string(),
+            to_role: "admin".to_string(),
+            with_grant_option: true,
+        };
+        let (observed, _) = IntoSql::<Pg>::into_sql_str(&grant).unwrap();
+        assert_eq!(observed, "GRANT ALL ON posts TO admin WITH GRANT OPTION");
+    }
+
+    #[test]
+    fn no_privileges() {
+        let grant = Grant {
+            privileges: Vec::new(),
+            on_table: "posts".to_string(),
+            to_role: "reader".to_string(),
+            with_grant_option: false,
+        };
+        assert!(matches!(IntoSql::<Pg>::into_sql_str(&grant), Err(Error::InvalidSchema(_))));
+    }
+}
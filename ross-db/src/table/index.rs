@@ -0,0 +1,187 @@
+use std::fmt;
+
+use diesel::pg::Pg;
+
+use crate::Error;
+use crate::table::fields::{IntoSql, SqlWrite, TableDefn};
+use crate::table::format::FormatOptions;
+use crate::table::quote;
+
+
+/// The access method of an index, rendered as `USING <method>`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum IndexMethod {
+    BTree,
+    Hash,
+    Gin,
+    Gist,
+    Brin,
+}
+
+impl IntoSql<Pg> for IndexMethod {
+    fn into_sql_fmt(&self, writer: &mut dyn fmt::Write) -> Result<usize, Error> {
+        let method = match self {
+            Self::BTree => "btree",
+            Self::Hash => "hash",
+            Self::Gin => "gin",
+            Self::Gist => "gist",
+            Self::Brin => "brin",
+        };
+        writer.write_sql(method)
+    }
+}
+
+
+/// A `CREATE INDEX` statement.
+///
+/// Names are kept unquoted and quoted while rendering if `quote_identifiers` is set.
+#[derive(Debug, Default)]
+pub struct Index {
+    pub name: String,
+    pub schema: Option<String>,
+    pub table: String,
+    pub columns: Vec<String>,
+    pub unique: bool,
+    /// Leave unset for the server default, btree.
+    pub method: Option<IndexMethod>,
+    /// Makes this a partial index; written verbatim after `WHERE`.
+    pub predicate: Option<String>,
+    pub quote_identifiers: bool,
+}
+
+impl TableDefn {
+    /// An index called `name` over `columns` of this table.
+    pub fn index<I, S>(&self, name: impl Into<String>, columns: I) -> Index
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Index {
+            name: name.into(),
+            schema: self.options.schema.clone(),
+            table: self.options.name.clone(),
+            columns: columns.into_iter().map(Into::into).collect(),
+            quote_identifiers: self.options.quote_identifiers,
+            ..Default::default()
+        }
+    }
+}
+
+impl Index {
+    /// Render the `CREATE INDEX` statement, spelling keywords as `format` asks.
+    pub fn into_sql_with_options(&self, writer: &mut dyn fmt::Write, format: &FormatOptions) -> Result<usize, Error> {
+        if self.columns.is_empty() {
+            return Err(Error::InvalidSchema(format!("index {} must cover at least one column", self.name)));
+        }
+
+        let quote = |name: &str| quote::identifier(name, self.quote_identifiers).into_owned();
+
+        let mut total_bytes = 0;
+        total_bytes += format.keyword(writer, "CREATE ")?;
+        if self.unique {
+            total_bytes += format.keyword(writer, "UNIQUE ")?;
+        }
+        total_bytes += format.keyword(writer, "INDEX ")?;
+        total_bytes += writer.write_sql(&quote(&self.name))?;
+        total_bytes += format.keyword(writer, " ON ")?;
+        if let Some(schema) = &self.schema {
+            total_bytes += writer.write_sql(&quote(schema))?;
+            total_bytes += writer.write_sql(".")?;
+        }
+        total_bytes += writer.write_sql(&quote(&self.table))?;
+        if let Some(method) = &self.method {
+            total_bytes += format.keyword(writer, " USING ")?;
+            total_bytes += method.into_sql_fmt(writer)?;
+        }
+        total_bytes += writer.write_sql(" (")?;
+        total_bytes += writer.write_sql(&self.columns.iter().map(|column| quote(column)).collect::<Vec<_>>().join(", "))?;
+        total_bytes += writer.write_sql(")")?;
+        if let Some(predicate) = &self.predicate {
+            total_bytes += format.keyword(writer, " WHERE ")?;
+            total_bytes += writer.write_sql(predicate)?;
+        }
+        Ok(total_bytes)
+    }
+}
+
+impl IntoSql<Pg> for Index {
+    fn into_sql_fmt(&self, writer: &mut dyn fmt::Write) -> Result<usize, Error> {
+        self.into_sql_with_options(writer, &FormatOptions::default())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::table::fields::Field;
+    use crate::table::posts;
+
+    #[test]
+    fn plain_index() {
+        let index = posts().index("posts_author_id_idx", ["author_id"]);
+        let (observed, _) = IntoSql::<Pg>::into_sql_str(&index).unwrap();
+        assert_eq!(observed, "CREATE INDEX posts_author_id_idx ON posts (author_id)");
+    }
+
+    #[test]
+    fn unique_index() {
+        let index = Index { unique: true, ..posts().index("posts_slug_key", ["slug"]) };
+        let (observed, _) = IntoSql::<Pg>::into_sql_str(&index).unwrap();
+        assert_eq!(observed, "CREATE UNIQUE INDEX posts_slug_key ON posts (slug)");
+    }
+
+    #[test]
+    fn multi_column_index() {
+        let index = posts().index("posts_author_slug_idx", ["author_id", "slug"]);
+        let (observed, _) = IntoSql::<Pg>::into_sql_str(&index).unwrap();
+        assert_eq!(observed, "CREATE INDEX posts_author_slug_idx ON posts (author_id, slug)");
+    }
+
+    #[test]
+    fn partial_index() {
+        let index = Index {
+            predicate: Some("deleted_at IS NULL".to_string()),
+            ..posts().index("posts_live_slug_idx", ["slug"])
+        };
+        let (observed, _) = IntoSql::<Pg>::into_sql_str(&index).unwrap();
+        assert_eq!(observed, "CREATE INDEX posts_live_slug_idx ON posts (slug) WHERE deleted_at IS NULL");
+    }
+
+    #[test]
+    fn index_methods() {
+        let index = Index { method: Some(IndexMethod::Gin), ..posts().index("posts_slug_gin", ["slug"]) };
+        let (observed, _) = IntoSql::<Pg>::into_sql_str(&index).unwrap();
+        assert_eq!(observed, "CREATE INDEX posts_slug_gin ON posts USING gin (slug)");
+
+        let index = Index { method: Some(IndexMethod::Gist), ..posts().index("posts_slug_gist", ["slug"]) };
+        let (observed, _) = IntoSql::<Pg>::into_sql_str(&index).unwrap();
+        assert_eq!(observed, "CREATE INDEX posts_slug_gist ON posts USING gist (slug)");
+    }
+
+    #[test]
+    fn default_method_omits_using() {
+        let (observed, _) = IntoSql::<Pg>::into_sql_str(&posts().index("posts_slug_idx", ["slug"])).unwrap();
+        assert!(!observed.contains("USING"));
+    }
+
+    #[test]
+    fn quoted_index() {
+        let mut orders = TableDefn::builder("Orders")
+            .quote_identifiers()
+            .column("order", Field::Integer, |c| c)
+            .column("userId", Field::Integer, |c| c)
+            .build();
+        orders.options.schema = Some("shop".to_string());
+        let index = orders.index("Orders_order_idx", ["order", "userId"]);
+        let (observed, _) = IntoSql::<Pg>::into_sql_str(&index).unwrap();
+        assert_eq!(observed, "CREATE INDEX \"Orders_order_idx\" ON shop.\"Orders\" (\"order\", \"userId\")");
+    }
+
+    #[test]
+    fn index_without_columns() {
+        let index = posts().index("posts_empty_idx", Vec::<String>::new());
+        assert!(matches!(IntoSql::<Pg>::into_sql_str(&index), Err(Error::InvalidSchema(_))));
+    }
+}
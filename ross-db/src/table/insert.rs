@@ -0,0 +1,242 @@
+use std::fmt;
+
+use diesel::backend::Backend;
+use diesel::pg::Pg;
+
+use crate::Error;
+use crate::table::fields::{IntoSql, SqlWrite, TableDefn};
+use crate::table::quote;
+
+
+/// A single-row `INSERT INTO table (columns) VALUES (values)` statement.
+///
+/// Values are written verbatim, so quoting and placeholders are up to the caller.
+/// Names are kept unquoted and quoted while rendering if `quote_identifiers` is set.
+#[derive(Debug, Default)]
+pub struct Insert {
+    pub schema: Option<String>,
+    pub table: String,
+    pub columns: Vec<String>,
+    pub values: Vec<String>,
+    pub quote_identifiers: bool,
+}
+
+/// A single-row `INSERT` with one bind placeholder per column, for prepared statements.
+///
+/// The placeholder style depends on the backend: `$1, $2, ...` for Postgres and
+/// `?` for MySQL and SQLite. Names are kept unquoted so each backend can quote them its own way.
+#[derive(Debug, Default)]
+pub struct InsertPlaceholders {
+    pub schema: Option<String>,
+    pub table: String,
+    pub columns: Vec<String>,
+    pub quote_identifiers: bool,
+}
+
+impl TableDefn {
+    /// Build an `INSERT` of one row from `(column, value)` pairs.
+    ///
+    /// Every column has to exist in this definition and appear only once.
+    pub fn insert(&self, values: &[(&str, &str)]) -> Result<Insert, Error> {
+        let columns: Vec<&str> = values.iter().map(|(column, _)| *column).collect();
+        self.check_columns(&columns)?;
+
+        Ok(Insert {
+            schema: self.options.schema.clone(),
+            table: self.options.name.clone(),
+            columns: columns.iter().map(|column| column.to_string()).collect(),
+            values: values.iter().map(|(_, value)| value.to_string()).collect(),
+            quote_identifiers: self.options.quote_identifiers,
+        })
+    }
+
+    /// Render the `INSERT` built by [`TableDefn::insert`].
+    pub fn insert_stmt(&self, values: &[(&str, &str)]) -> Result<String, Error> {
+        let (sql, _) = IntoSql::<Pg>::into_sql_str(&self.insert(values)?)?;
+        Ok(sql)
+    }
+
+    /// Render an `INSERT` of the given columns with bind placeholders in `B`'s style.
+    ///
+    /// ```
+    /// use diesel::pg::Pg;
+    /// use ross_db::table::fields::{Field, TableDefn};
+    ///
+    /// let posts = TableDefn::builder("posts")
+    ///     .column("title", Field::Text, |c| c)
+    ///     .build();
+    /// let sql = posts.insert_placeholders::<Pg>(&["title"]).unwrap();
+    /// assert_eq!(sql, "INSERT INTO posts (title) VALUES ($1)");
+    /// ```
+    pub fn insert_placeholders<B: Backend>(&self, columns: &[&str]) -> Result<String, Error>
+    where
+        InsertPlaceholders: IntoSql<B>,
+    {
+        self.check_columns(columns)?;
+
+        let insert = InsertPlaceholders {
+            schema: self.options.schema.clone(),
+            table: self.options.name.clone(),
+            columns: columns.iter().map(|column| column.to_string()).collect(),
+            quote_identifiers: self.options.quote_identifiers,
+        };
+        let (sql, _) = IntoSql::<B>::into_sql_str(&insert)?;
+        Ok(sql)
+    }
+
+    fn check_columns(&self, columns: &[&str]) -> Result<(), Error> {
+        if columns.is_empty() {
+            return Err(Error::InvalidSchema(format!("insert into {} needs at least one column", self.options.name)));
+        }
+        for (index, column) in columns.iter().enumerate() {
+            if !self.fields.iter().any(|field| field.options.name == *column) {
+                return Err(Error::InvalidSchema(format!("table {} has no column {}", self.options.name, column)));
+            }
+            if columns[..index].contains(column) {
+                return Err(Error::InvalidSchema(format!("insert into {} names column {} twice", self.options.name, column)));
+            }
+        }
+        Ok(())
+    }
+}
+
+fn write_insert(
+    writer: &mut dyn fmt::Write,
+    schema: Option<&str>,
+    table: &str,
+    columns: &[String],
+    values: impl Iterator<Item = String>,
+    quote: impl Fn(&str) -> String,
+) -> Result<usize, Error> {
+    let mut total_bytes = 0;
+    total_bytes += writer.write_sql("INSERT INTO ")?;
+    if let Some(schema) = schema {
+        total_bytes += writer.write_sql(&quote(schema))?;
+        total_bytes += writer.write_sql(".")?;
+    }
+    total_bytes += writer.write_sql(&quote(table))?;
+    total_bytes += writer.write_sql(" (")?;
+    for (index, column) in columns.iter().enumerate() {
+        if index > 0 {
+            total_bytes += writer.write_sql(", ")?;
+        }
+        total_bytes += writer.write_sql(&quote(column))?;
+    }
+    total_bytes += writer.write_sql(") VALUES (")?;
+    for (index, value) in values.enumerate() {
+        if index > 0 {
+            total_bytes += writer.write_sql(", ")?;
+        }
+        total_bytes += writer.write_sql(&value)?;
+    }
+    total_bytes += writer.write_sql(")")?;
+    Ok(total_bytes)
+}
+
+impl InsertPlaceholders {
+    pub(crate) fn write_with(
+        &self,
+        writer: &mut dyn fmt::Write,
+        quote: impl Fn(&str) -> String,
+        placeholder: impl Fn(usize) -> String,
+    ) -> Result<usize, Error> {
+        let placeholders = (1..=self.columns.len()).map(placeholder);
+        write_insert(writer, self.schema.as_deref(), &self.table, &self.columns, placeholders, quote)
+    }
+}
+
+impl IntoSql<Pg> for InsertPlaceholders {
+    fn into_sql_fmt(&self, writer: &mut dyn fmt::Write) -> Result<usize, Error> {
+        self.write_with(
+            writer,
+            |name| quote::identifier(name, self.quote_identifiers).into_owned(),
+            |index| format!("${}", index),
+        )
+    }
+}
+
+impl IntoSql<Pg> for Insert {
+    fn into_sql_fmt(&self, writer: &mut dyn fmt::Write) -> Result<usize, Error> {
+        if self.columns.len() != self.values.len() {
+            return Err(Error::InvalidSchema(format!(
+                "insert into {} has {} columns but {} values",
+                self.table,
+                self.columns.len(),
+                self.values.len()
+            )));
+        }
+        write_insert(
+            writer,
+            self.schema.as_deref(),
+            &self.table,
+            &self.columns,
+            self.values.iter().cloned(),
+            |name| quote::identifier(name, self.quote_identifiers).into_owned(),
+        )
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::table::fields::Field;
+    use crate::table::posts;
+
+    #[test]
+    fn single_row() {
+        let observed = posts().insert_stmt(&[("title", "'hi'"), ("published", "false")]).unwrap();
+        assert_eq!(observed, "INSERT INTO posts (title, published) VALUES ('hi', false)");
+    }
+
+    #[test]
+    fn quoted_names() {
+        let mut orders = TableDefn::builder("Orders")
+            .quote_identifiers()
+            .column("order", Field::Integer, |c| c)
+            .build();
+        orders.options.schema = Some("shop".to_string());
+        let insert = orders.insert(&[("order", "1")]).unwrap();
+        assert_eq!(insert.table, "Orders");
+        assert_eq!(insert.columns, ["order"]);
+        let (observed, _) = IntoSql::<Pg>::into_sql_str(&insert).unwrap();
+        assert_eq!(observed, "INSERT INTO shop.\"Orders\" (\"order\") VALUES (1)");
+    }
+
+    #[test]
+    fn columns_without_values() {
+        let insert = Insert { table: "posts".to_string(), columns: vec!["title".to_string()], ..Default::default() };
+        assert!(matches!(IntoSql::<Pg>::into_sql_str(&insert), Err(Error::InvalidSchema(_))));
+    }
+
+    #[test]
+    fn unknown_column() {
+        assert!(matches!(
+            posts().insert_stmt(&[("title", "'hi'"), ("author", "'me'")]),
+            Err(Error::InvalidSchema(_))
+        ));
+    }
+
+    #[test]
+    fn duplicate_column() {
+        assert!(matches!(
+            posts().insert_stmt(&[("title", "'hi'"), ("title", "'again'")]),
+            Err(Error::InvalidSchema(_))
+        ));
+        assert!(matches!(
+            posts().insert_placeholders::<Pg>(&["title", "published", "title"]),
+            Err(Error::InvalidSchema(_))
+        ));
+    }
+
+    #[test]
+    fn postgres_placeholders() {
+        let observed = posts().insert_placeholders::<Pg>(&["title", "published"]).unwrap();
+        assert_eq!(observed, "INSERT INTO posts (title, published) VALUES ($1, $2)");
+
+        assert!(matches!(
+            posts().insert_placeholders::<Pg>(&["author"]),
+            Err(Error::InvalidSchema(_))
+        ));
+    }
+}
@@ -1 +1,32 @@
-pub mod fields;
\ No newline at end of file
+pub mod alter;
+pub mod builder;
+pub mod constraints;
+pub mod describe;
+pub mod diesel_schema;
+pub mod drop;
+pub mod enums;
+pub mod extension;
+pub mod fields;
+pub mod format;
+pub mod grant;
+pub mod index;
+pub mod insert;
+pub mod mysql;
+mod parse;
+pub mod partition;
+mod quote;
+pub mod sequence;
+pub mod sqlite;
+pub mod view;
+
+
+#[cfg(test)]
+pub(crate) fn posts() -> fields::TableDefn {
+    fields::TableDefn::builder("posts")
+        .column("id", fields::Field::Serial, |c| c.primary_key())
+        .column("author_id", fields::Field::Integer, |c| c)
+        .column("slug", fields::Field::Text, |c| c)
+        .column("title", fields::Field::Text, |c| c.not_null())
+        .column("published", fields::Field::Boolean, |c| c.not_null())
+        .build()
+}
@@ -0,0 +1,458 @@
+use std::fmt;
+
+use diesel::mysql::Mysql;
+
+use crate::Error;
+use crate::table::constraints::Deferrable;
+use crate::table::fields::{Field, IdentityKind, IntoSql, SqlWrite, TableDefn, TableField};
+use crate::table::format::FormatOptions;
+use crate::table::insert::InsertPlaceholders;
+use crate::table::quote;
+
+
+fn quote_identifier(identifier: &str) -> String {
+    format!("`{}`", identifier.replace('`', "``"))
+}
+
+fn quoted_column_list(columns: &[String]) -> String {
+    columns
+        .iter()
+        .map(|column| quote_identifier(column))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn write_fractional(writer: &mut dyn fmt::Write, type_name: &str, precision: Option<u8>) -> Result<usize, Error> {
+    match precision {
+        Some(precision) => writer.write_sql_fmt(format_args!("{}({})", type_name, precision)),
+        None => writer.write_sql(type_name),
+    }
+}
+
+
+impl IntoSql<Mysql> for Field {
+    fn into_sql_fmt(&self, writer: &mut dyn fmt::Write) -> Result<usize, Error> {
+        self.validate()?;
+        match self {
+            Self::Char { max_length: Some(max_length) } => writer.write_sql_fmt(format_args!("CHAR({})", *max_length)),
+            Self::Char { max_length: None } => writer.write_sql("CHAR"),
+            Self::VarChar { max_length: Some(max_length) } => writer.write_sql_fmt(format_args!("VARCHAR({})", *max_length)),
+            Self::VarChar { max_length: None } => writer.write_sql("VARCHAR(255)"),
+            Self::Text => writer.write_sql("TEXT"),
+            Self::SmallSerial => writer.write_sql("SMALLINT AUTO_INCREMENT"),
+            Self::Serial => writer.write_sql("INT AUTO_INCREMENT"),
+            Self::BigSerial => writer.write_sql("BIGINT AUTO_INCREMENT"),
+            Self::SmallInt => writer.write_sql("SMALLINT"),
+            Self::Integer => writer.write_sql("INT"),
+            Self::BigInt => writer.write_sql("BIGINT"),
+            Self::Real => writer.write_sql("FLOAT"),
+            Self::DoublePrecision => writer.write_sql("DOUBLE"),
+            Self::Numeric { precision, scale: Some(scale) } => writer.write_sql_fmt(format_args!("DECIMAL({}, {})", *precision, *scale)),
+            Self::Numeric { precision, scale: None } => writer.write_sql_fmt(format_args!("DECIMAL({})", *precision)),
+            Self::Money => writer.write_sql("DECIMAL(19, 4)"),
+            Self::Boolean => writer.write_sql("TINYINT(1)"),
+            Self::Date => writer.write_sql("DATE"),
+            Self::Time { precision, .. } => write_fractional(writer, "TIME", *precision),
+            Self::Timestamp { with_timezone: false, precision } => write_fractional(writer, "DATETIME", *precision),
+            Self::Timestamp { with_timezone: true, precision } => write_fractional(writer, "TIMESTAMP", *precision),
+            Self::Uuid => writer.write_sql("CHAR(36)"),
+            Self::Json | Self::Jsonb => writer.write_sql("JSON"),
+            Self::Bytea => writer.write_sql("BLOB"),
+            Self::Bit { length } => writer.write_sql_fmt(format_args!("BIT({})", *length)),
+            Self::Array(_) | Self::BitVarying { .. } | Self::Interval { .. } | Self::Inet | Self::Cidr | Self::MacAddr | Self::Enum { .. } => {
+                Err(Error::InvalidSchema(format!("{:?} columns are not supported by MySQL", self)))
+            },
+        }
+    }
+}
+
+impl TableField {
+    fn write_mysql(&self, writer: &mut dyn fmt::Write, inline_primary_key: bool) -> Result<usize, Error> {
+        // InnoDB ignores inline references and MySQL can't name an inline UNIQUE, so the
+        // table adds both as constraints instead.
+        self.validate()?;
+
+        let mut total_bytes = 0;
+        total_bytes += writer.write_sql(&quote_identifier(&self.options.name))?;
+        total_bytes += writer.write_sql(" ")?;
+
+        total_bytes += IntoSql::<Mysql>::into_sql_fmt(&self.kind, writer)?;
+
+        if let Some(collation) = &self.options.collation {
+            total_bytes += writer.write_sql(" COLLATE ")?;
+            total_bytes += writer.write_sql(collation)?;
+        }
+
+        if let Some(generated) = &self.options.generated {
+            total_bytes += writer.write_sql(" ")?;
+            total_bytes += generated.write_pg(writer, &FormatOptions::default())?;
+        }
+
+        if let Some(default) = &self.options.default {
+            total_bytes += writer.write_sql(" DEFAULT ")?;
+            total_bytes += writer.write_sql(default)?;
+        }
+
+        match self.options.identity {
+            Some(IdentityKind::ByDefault) => total_bytes += writer.write_sql(" AUTO_INCREMENT")?,
+            Some(IdentityKind::Always) => {
+                return Err(Error::InvalidSchema(format!(
+                    "identity column {} cannot be GENERATED ALWAYS on MySQL",
+                    self.options.name
+                )));
+            },
+            None => {},
+        }
+
+        let mut constraints = Vec::new();
+        if let Some(null_constraint) = self.options.null {
+            constraints.push(if null_constraint {
+                "NULL"
+            } else {
+                "NOT NULL"
+            });
+        }
+        if self.options.primary_key && inline_primary_key {
+            constraints.push("PRIMARY KEY");
+        }
+        if self.options.unique && self.options.unique_constraint.is_none() {
+            constraints.push("UNIQUE");
+        }
+
+        if !constraints.is_empty() {
+            total_bytes += writer.write_sql(" ")?;
+            total_bytes += writer.write_sql(&constraints.join(" "))?;
+        }
+
+        if let Some(check) = &self.options.check {
+            total_bytes += writer.write_sql(" ")?;
+            total_bytes += write_check(writer, self.options.check_constraint.as_deref(), check)?;
+        }
+
+        if let Some(values) = &self.options.allowed_values {
+            let check = format!("{} IN ({})", quote_identifier(&self.options.name), quote::literal_list(values));
+            total_bytes += writer.write_sql(" ")?;
+            total_bytes += write_check(writer, None, &check)?;
+        }
+
+        Ok(total_bytes)
+    }
+}
+
+impl IntoSql<Mysql> for TableField {
+    fn into_sql_fmt(&self, writer: &mut dyn fmt::Write) -> Result<usize, Error> {
+        self.write_mysql(writer, true)
+    }
+}
+
+fn write_check(writer: &mut dyn fmt::Write, name: Option<&str>, check: &str) -> Result<usize, Error> {
+    let mut total_bytes = write_constraint_name(writer, name)?;
+    total_bytes += writer.write_sql("CHECK (")?;
+    total_bytes += writer.write_sql(check)?;
+    total_bytes += writer.write_sql(")")?;
+    Ok(total_bytes)
+}
+
+fn write_unique(writer: &mut dyn fmt::Write, name: Option<&str>, columns: &[String]) -> Result<usize, Error> {
+    let mut total_bytes = write_constraint_name(writer, name)?;
+    total_bytes += writer.write_sql("UNIQUE (")?;
+    total_bytes += writer.write_sql(&quoted_column_list(columns))?;
+    total_bytes += writer.write_sql(")")?;
+    Ok(total_bytes)
+}
+
+fn write_constraint_name(writer: &mut dyn fmt::Write, name: Option<&str>) -> Result<usize, Error> {
+    match name {
+        Some(name) => Ok(writer.write_sql("CONSTRAINT ")? + writer.write_sql(&quote_identifier(name))? + writer.write_sql(" ")?),
+        None => Ok(0),
+    }
+}
+
+fn reject_deferrable(deferrable: &Option<Deferrable>, table: &str) -> Result<(), Error> {
+    match deferrable {
+        Some(_) => Err(Error::InvalidSchema(format!("foreign key to {} cannot be deferrable on MySQL", table))),
+        None => Ok(()),
+    }
+}
+
+impl IntoSql<Mysql> for TableDefn {
+    fn into_sql_fmt(&self, writer: &mut dyn fmt::Write) -> Result<usize, Error> {
+        self.validate()?;
+        if !self.options.exclusions.is_empty() {
+            return Err(Error::InvalidSchema(format!("table {} has EXCLUDE constraints, which MySQL does not support", self.options.name)));
+        }
+        // MySQL allows one AUTO_INCREMENT column, and only as a key.
+        let sole_primary_key = self.sole_primary_key();
+        if let Some(field) = self.fields.iter().find(|field| field.kind.is_serial() && sole_primary_key != Some(field.options.name.as_str())) {
+            return Err(Error::InvalidSchema(format!(
+                "serial column {} must be the only primary key column of {} on MySQL",
+                field.options.name,
+                self.options.name
+            )));
+        }
+
+        let mut total_bytes = 0;
+        total_bytes += writer.write_sql("CREATE ")?;
+
+        // MySQL only knows session-local temporary tables.
+        if self.options.kind.is_some() {
+            total_bytes += writer.write_sql("TEMPORARY ")?;
+        }
+
+        total_bytes += writer.write_sql("TABLE ")?;
+        if self.options.if_not_exists {
+            total_bytes += writer.write_sql("IF NOT EXISTS ")?;
+        }
+        if let Some(schema) = &self.options.schema {
+            total_bytes += writer.write_sql(&quote_identifier(schema))?;
+            total_bytes += writer.write_sql(".")?;
+        }
+        total_bytes += writer.write_sql(&quote_identifier(&self.options.name))?;
+
+        total_bytes += writer.write_sql(" (\n\t")?;
+
+        let inline_primary_key = self.options.primary_key.is_empty();
+
+        for (index, field) in self.fields.iter().enumerate() {
+            if index > 0 {
+                total_bytes += writer.write_sql(",\n\t")?;
+            }
+            total_bytes += field.write_mysql(writer, inline_primary_key)?;
+        }
+
+        if !inline_primary_key {
+            total_bytes += writer.write_sql(",\n\tPRIMARY KEY (")?;
+            total_bytes += writer.write_sql(&quoted_column_list(&self.options.primary_key))?;
+            total_bytes += writer.write_sql(")")?;
+        }
+
+        for field in &self.fields {
+            if let (true, Some(name)) = (field.options.unique, &field.options.unique_constraint) {
+                total_bytes += writer.write_sql(",\n\t")?;
+                total_bytes += write_unique(writer, Some(name), std::slice::from_ref(&field.options.name))?;
+            }
+        }
+
+        for (name, columns) in &self.options.unique_constraints {
+            total_bytes += writer.write_sql(",\n\t")?;
+            total_bytes += write_unique(writer, name.as_deref(), columns)?;
+        }
+
+        for field in &self.fields {
+            if let Some(references) = &field.options.references {
+                reject_deferrable(&references.deferrable, &references.table)?;
+                total_bytes += writer.write_sql(",\n\tFOREIGN KEY (")?;
+                total_bytes += writer.write_sql(&quote_identifier(&field.options.name))?;
+                total_bytes += writer.write_sql(") ")?;
+                total_bytes += references.write_with(writer, &FormatOptions::default(), quote_identifier)?;
+            }
+        }
+
+        for foreign_key in &self.options.foreign_keys {
+            reject_deferrable(&foreign_key.deferrable, &foreign_key.table)?;
+            total_bytes += writer.write_sql(",\n\t")?;
+            total_bytes += foreign_key.write_with(writer, &FormatOptions::default(), quote_identifier)?;
+        }
+
+        for (name, check) in &self.options.checks {
+            total_bytes += writer.write_sql(",\n\t")?;
+            total_bytes += write_check(writer, name.as_deref(), check)?;
+        }
+
+        total_bytes += writer.write_sql("\n)")?;
+        Ok(total_bytes)
+    }
+}
+
+impl IntoSql<Mysql> for InsertPlaceholders {
+    fn into_sql_fmt(&self, writer: &mut dyn fmt::Write) -> Result<usize, Error> {
+        self.write_with(writer, quote_identifier, |_| "?".to_string())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::table::fields::{CommonFieldOptions, CommonTableOptions};
+
+    #[test]
+    fn table() {
+        let posts = TableDefn {
+            options: CommonTableOptions { name: "posts".to_string(), if_not_exists: true, kind: None, ..Default::default() },
+            fields: vec![
+                TableField {
+                    options: CommonFieldOptions {
+                        name: "id".to_string(),
+                        primary_key: true,
+                        unique: false,
+                        null: None,
+                        ..Default::default()
+                    },
+                    kind: Field::Serial
+                },
+                TableField {
+                    options: CommonFieldOptions {
+                        name: "title".to_string(),
+                        primary_key: false,
+                        unique: false,
+                        null: Some(false),
+                        ..Default::default()
+                    },
+                    kind: Field::VarChar { max_length: None }
+                },
+                TableField {
+                    options: CommonFieldOptions {
+                        name: "published".to_string(),
+                        primary_key: false,
+                        unique: false,
+                        null: Some(false),
+                        ..Default::default()
+                    },
+                    kind: Field::Boolean
+                },
+            ],
+        };
+        let (observed, _) = IntoSql::<Mysql>::into_sql_str(&posts).unwrap();
+        let expected = "CREATE TABLE IF NOT EXISTS `posts` (\n\t`id` INT AUTO_INCREMENT PRIMARY KEY,\n\t`title` VARCHAR(255) NOT NULL,\n\t`published` TINYINT(1) NOT NULL\n)";
+        assert_eq!(observed, expected);
+    }
+
+    #[test]
+    fn composite_primary_key() {
+        let memberships = TableDefn::builder("memberships")
+            .column("user_id", Field::Integer, |c| c.primary_key())
+            .column("group_id", Field::Integer, |c| c.primary_key())
+            .primary_key(["user_id", "group_id"])
+            .build();
+        let (observed, _) = IntoSql::<Mysql>::into_sql_str(&memberships).unwrap();
+        let expected = "CREATE TABLE `memberships` (\n\t`user_id` INT,\n\t`group_id` INT,\n\tPRIMARY KEY (`user_id`, `group_id`)\n)";
+        assert_eq!(observed, expected);
+    }
+
+    #[test]
+    fn serial_outside_the_primary_key() {
+        let posts = TableDefn::builder("posts")
+            .column("id", Field::Integer, |c| c.primary_key())
+            .column("position", Field::Serial, |c| c)
+            .build();
+        assert!(matches!(IntoSql::<Mysql>::into_sql_str(&posts), Err(Error::InvalidSchema(_))));
+
+        let memberships = TableDefn::builder("memberships")
+            .column("id", Field::Serial, |c| c)
+            .column("group_id", Field::Integer, |c| c)
+            .primary_key(["id", "group_id"])
+            .build();
+        assert!(matches!(IntoSql::<Mysql>::into_sql_str(&memberships), Err(Error::InvalidSchema(_))));
+
+        let posts = TableDefn::builder("posts")
+            .column("id", Field::BigSerial, |c| c)
+            .primary_key(["id"])
+            .build();
+        let (observed, _) = IntoSql::<Mysql>::into_sql_str(&posts).unwrap();
+        assert_eq!(observed, "CREATE TABLE `posts` (\n\t`id` BIGINT AUTO_INCREMENT,\n\tPRIMARY KEY (`id`)\n)");
+    }
+
+    #[test]
+    fn constraints() {
+        use crate::table::constraints::{ReferentialAction, TableForeignKey};
+
+        let mut orders = TableDefn::builder("orders")
+            .column("id", Field::BigInt, |c| c.primary_key().identity(IdentityKind::ByDefault))
+            .column("user_id", Field::Integer, |c| {
+                c.not_null().references("users", "id").references.as_mut().unwrap().on_delete = Some(ReferentialAction::Cascade);
+                c
+            })
+            .column("code", Field::VarChar { max_length: Some(12) }, |c| c.collation("utf8mb4_bin").unique_named("orders_code_key"))
+            .column("quantity", Field::Integer, |c| c.default_value("1").check("quantity > 0"))
+            .column("region", Field::Text, |c| c)
+            .column("total", Field::Integer, |c| c.generated("quantity * 2", true))
+            .unique(Some("orders_region_key"), ["region", "code"])
+            .check(None, "total >= quantity")
+            .build();
+        orders.options.foreign_keys.push(TableForeignKey {
+            columns: vec!["region".to_string()],
+            table: "regions".to_string(),
+            referenced_columns: vec!["name".to_string()],
+            ..Default::default()
+        });
+        let (observed, _) = IntoSql::<Mysql>::into_sql_str(&orders).unwrap();
+        let expected = concat!(
+            "CREATE TABLE `orders` (\n",
+            "\t`id` BIGINT AUTO_INCREMENT PRIMARY KEY,\n",
+            "\t`user_id` INT NOT NULL,\n",
+            "\t`code` VARCHAR(12) COLLATE utf8mb4_bin,\n",
+            "\t`quantity` INT DEFAULT 1 CHECK (quantity > 0),\n",
+            "\t`region` TEXT,\n",
+            "\t`total` INT GENERATED ALWAYS AS (quantity * 2) STORED,\n",
+            "\tCONSTRAINT `orders_code_key` UNIQUE (`code`),\n",
+            "\tCONSTRAINT `orders_region_key` UNIQUE (`region`, `code`),\n",
+            "\tFOREIGN KEY (`user_id`) REFERENCES `users`(`id`) ON DELETE CASCADE,\n",
+            "\tFOREIGN KEY (`region`) REFERENCES `regions` (`name`),\n",
+            "\tCHECK (total >= quantity)\n",
+            ")",
+        );
+        assert_eq!(observed, expected);
+    }
+
+    #[test]
+    fn inexpressible_options() {
+        use crate::table::constraints::{Deferrable, ExcludeConstraint};
+        use crate::table::index::IndexMethod;
+
+        let always = TableDefn::builder("orders")
+            .column("id", Field::BigInt, |c| c.identity(IdentityKind::Always))
+            .build();
+        assert!(matches!(IntoSql::<Mysql>::into_sql_str(&always), Err(Error::InvalidSchema(_))));
+
+        let mut deferred = TableDefn::builder("orders")
+            .column("user_id", Field::Integer, |c| c.references("users", "id"))
+            .build();
+        deferred.fields[0].options.references.as_mut().unwrap().deferrable = Some(Deferrable { deferrable: true, initially_deferred: true });
+        assert!(matches!(IntoSql::<Mysql>::into_sql_str(&deferred), Err(Error::InvalidSchema(_))));
+
+        let mut bookings = TableDefn::builder("bookings").column("room", Field::Integer, |c| c).build();
+        bookings.options.exclusions.push(ExcludeConstraint {
+            name: None,
+            method: IndexMethod::Gist,
+            elements: vec![("room".to_string(), "=".to_string())],
+        });
+        assert!(matches!(IntoSql::<Mysql>::into_sql_str(&bookings), Err(Error::InvalidSchema(_))));
+    }
+
+    #[test]
+    fn unsupported_field() {
+        assert!(matches!(
+            IntoSql::<Mysql>::into_sql_str(&Field::Inet),
+            Err(Error::InvalidSchema(_))
+        ));
+    }
+
+    #[test]
+    fn unsupported_column_in_table() {
+        let hosts = TableDefn {
+            options: CommonTableOptions { name: "hosts".to_string(), ..Default::default() },
+            fields: vec![
+                TableField {
+                    options: CommonFieldOptions { name: "id".to_string(), primary_key: true, ..Default::default() },
+                    kind: Field::Serial
+                },
+                TableField {
+                    options: CommonFieldOptions { name: "address".to_string(), ..Default::default() },
+                    kind: Field::Inet
+                },
+            ],
+        };
+        assert!(matches!(IntoSql::<Mysql>::into_sql_str(&hosts), Err(Error::InvalidSchema(_))));
+    }
+
+    #[test]
+    fn insert_placeholders() {
+        let posts = TableDefn::builder("posts")
+            .column("title", Field::Text, |c| c)
+            .column("body", Field::Text, |c| c)
+            .build();
+        let observed = posts.insert_placeholders::<Mysql>(&["title", "body"]).unwrap();
+        assert_eq!(observed, "INSERT INTO `posts` (`title`, `body`) VALUES (?, ?)");
+    }
+}
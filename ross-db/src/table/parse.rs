@@ -0,0 +1,481 @@
+//! Reads back the `CREATE TABLE` statements this crate generates.
+
+use crate::Error;
+use crate::table::fields::{CommonFieldOptions, CommonTableOptions, Field, TableDefn, TableField, TableKind};
+
+
+// Type names in the order they must be tried, longer spellings before their prefixes.
+const TYPE_NAMES: &[&str] = &[
+    "TIMESTAMP WITH TIME ZONE",
+    "TIMESTAMP",
+    "TIME WITH TIME ZONE",
+    "TIME",
+    "DOUBLE PRECISION",
+    "SMALLSERIAL",
+    "BIGSERIAL",
+    "SERIAL",
+    "SMALLINT",
+    "INTEGER",
+    "BIGINT",
+    "REAL",
+    "NUMERIC",
+    "MONEY",
+    "TEXT",
+    "BOOLEAN",
+    "DATE",
+    "UUID",
+    "JSONB",
+    "JSON",
+    "BYTEA",
+    "INET",
+    "CIDR",
+    "MACADDR",
+    "VARCHAR",
+    "CHARACTER",
+    "BPCHAR",
+    "CHAR",
+    "BIT VARYING",
+    "BIT",
+    "INTERVAL",
+];
+
+// The field restrictions `INTERVAL` accepts, longest first so prefixes don't match early.
+const INTERVAL_FIELDS: &[&str] = &[
+    "YEAR TO MONTH",
+    "DAY TO HOUR",
+    "DAY TO MINUTE",
+    "DAY TO SECOND",
+    "HOUR TO MINUTE",
+    "HOUR TO SECOND",
+    "MINUTE TO SECOND",
+    "YEAR",
+    "MONTH",
+    "DAY",
+    "HOUR",
+    "MINUTE",
+    "SECOND",
+];
+
+
+impl TableDefn {
+    /// Parse a `CREATE TABLE` statement as produced by this crate back into a definition.
+    ///
+    /// Only the table name, `IF NOT EXISTS`, `TEMPORARY`/`UNLOGGED`, a table-level
+    /// `PRIMARY KEY` and columns with their types and `NULL`/`NOT NULL`/`PRIMARY KEY`/`UNIQUE`
+    /// are understood; anything else is reported as [`Error::Parse`].
+    ///
+    /// A type name that is not a built-in type is read as a [`Field::Enum`], unless it is
+    /// unquoted, not schema-qualified and only a letter or two away from a built-in type,
+    /// so a typo like `INTERGER` is reported instead of being taken for an enum.
+    pub fn parse(sql: &str) -> Result<TableDefn, Error> {
+        Parser { rest: sql }.table()
+    }
+}
+
+
+// Whether `name` is a misspelling of the single-word type `type_name`: at most one edit away
+// from a short type name, two from a longer one, ignoring case.
+fn is_near_miss(name: &str, type_name: &str) -> bool {
+    if type_name.contains(' ') {
+        return false;
+    }
+    let name: Vec<char> = name.to_ascii_uppercase().chars().collect();
+    let type_name: Vec<char> = type_name.chars().collect();
+
+    // Levenshtein distance, one row at a time.
+    let mut previous: Vec<usize> = (0..=type_name.len()).collect();
+    for (i, a) in name.iter().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, b) in type_name.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a != b);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+
+    let distance = previous[type_name.len()];
+    distance > 0 && distance <= if type_name.len() > 4 { 2 } else { 1 }
+}
+
+
+struct Parser<'a> {
+    rest: &'a str,
+}
+
+impl<'a> Parser<'a> {
+    fn table(&mut self) -> Result<TableDefn, Error> {
+        let mut options = CommonTableOptions::default();
+
+        self.expect_keyword("CREATE")?;
+        if self.keyword("GLOBAL") {
+            options.kind = Some(TableKind::Global);
+            self.expect_keyword("TEMPORARY")?;
+        } else if self.keyword("LOCAL") {
+            options.kind = Some(TableKind::Local);
+            self.expect_keyword("TEMPORARY")?;
+        }
+        options.unlogged = self.keyword("UNLOGGED");
+        self.expect_keyword("TABLE")?;
+        options.if_not_exists = self.keyword("IF");
+        if options.if_not_exists {
+            self.expect_keyword("NOT")?;
+            self.expect_keyword("EXISTS")?;
+        }
+
+        let (name, quoted) = self.identifier()?;
+        options.quote_identifiers |= quoted;
+        if self.symbol(".") {
+            let (table, quoted) = self.identifier()?;
+            options.quote_identifiers |= quoted;
+            options.schema = Some(name);
+            options.name = table;
+        } else {
+            options.name = name;
+        }
+
+        self.expect_symbol("(")?;
+        let mut fields = Vec::new();
+        loop {
+            if self.keyword("PRIMARY") {
+                self.expect_keyword("KEY")?;
+                options.primary_key = self.identifier_list()?;
+            } else {
+                let (field, quoted) = self.field()?;
+                options.quote_identifiers |= quoted;
+                fields.push(field);
+            }
+            if !self.symbol(",") {
+                break;
+            }
+        }
+        self.expect_symbol(")")?;
+        self.symbol(";");
+
+        self.skip_whitespace();
+        if !self.rest.is_empty() {
+            return Err(self.unexpected());
+        }
+
+        Ok(TableDefn { fields, options })
+    }
+
+    fn field(&mut self) -> Result<(TableField, bool), Error> {
+        let (name, quoted) = self.identifier()?;
+        let kind = self.field_kind()?;
+        let mut options = CommonFieldOptions { name, ..Default::default() };
+
+        loop {
+            if self.keyword("NOT") {
+                self.expect_keyword("NULL")?;
+                options.null = Some(false);
+            } else if self.keyword("NULL") {
+                options.null = Some(true);
+            } else if self.keyword("PRIMARY") {
+                self.expect_keyword("KEY")?;
+                options.primary_key = true;
+            } else if self.keyword("UNIQUE") {
+                options.unique = true;
+            } else {
+                break;
+            }
+        }
+
+        Ok((TableField { options, kind }, quoted))
+    }
+
+    fn field_kind(&mut self) -> Result<Field, Error> {
+        let Some(type_name) = TYPE_NAMES.iter().find(|type_name| self.keyword(type_name)) else {
+            // Anything that is not a built-in type is taken to be a user-defined enum.
+            let (name, quoted) = self.identifier()?;
+            let (schema, type_name) = if self.symbol(".") {
+                (Some(name), self.identifier()?.0)
+            } else {
+                if let Some(type_name) = TYPE_NAMES.iter().find(|type_name| !quoted && is_near_miss(&name, type_name)) {
+                    return Err(Error::Parse(format!("unknown type {}, did you mean {}?", name, type_name)));
+                }
+                (None, name)
+            };
+            return Ok(self.array_suffix(Field::Enum { type_name, schema }));
+        };
+
+        let interval_fields = match *type_name {
+            "INTERVAL" => INTERVAL_FIELDS.iter().find(|fields| self.keyword(fields)).map(|fields| fields.to_string()),
+            _ => None,
+        };
+
+        let arguments = if self.symbol("(") {
+            let mut arguments = vec![self.number()?];
+            while self.symbol(",") {
+                arguments.push(self.number()?);
+            }
+            self.expect_symbol(")")?;
+            arguments
+        } else {
+            Vec::new()
+        };
+
+        let kind = match (*type_name, arguments.as_slice()) {
+            ("CHAR" | "CHARACTER" | "BPCHAR", [max_length]) => Field::Char { max_length: Some(*max_length) },
+            ("VARCHAR", [max_length]) => Field::VarChar { max_length: Some(*max_length) },
+            ("VARCHAR", []) => Field::VarChar { max_length: None },
+            ("BIT", [length]) => Field::Bit { length: *length },
+            ("BIT VARYING", [length]) => Field::BitVarying { length: Some(*length) },
+            ("BIT VARYING", []) => Field::BitVarying { length: None },
+            ("NUMERIC", [precision]) => Field::Numeric { precision: Self::numeric(*precision)?, scale: None },
+            ("NUMERIC", [precision, scale]) => Field::Numeric { precision: Self::numeric(*precision)?, scale: Some(Self::numeric(*scale)?) },
+            ("INTERVAL", [precision]) => Field::Interval { fields: interval_fields, precision: Some(Self::precision(*precision)?) },
+            ("INTERVAL", []) => Field::Interval { fields: interval_fields, precision: None },
+            ("TIMESTAMP", [precision]) => Field::Timestamp {
+                precision: Some(Self::precision(*precision)?),
+                with_timezone: self.keyword("WITH TIME ZONE"),
+            },
+            ("TIME", [precision]) => Field::Time {
+                precision: Some(Self::precision(*precision)?),
+                with_timezone: self.keyword("WITH TIME ZONE"),
+            },
+            (_, [_, ..]) => return Err(Error::Parse(format!("unexpected arguments to {}", type_name))),
+            ("CHAR" | "CHARACTER" | "BPCHAR", []) => Field::Char { max_length: None },
+            ("TIMESTAMP WITH TIME ZONE", []) => Field::Timestamp { with_timezone: true, precision: None },
+            ("TIMESTAMP", []) => Field::Timestamp { with_timezone: false, precision: None },
+            ("TIME WITH TIME ZONE", []) => Field::Time { with_timezone: true, precision: None },
+            ("TIME", []) => Field::Time { with_timezone: false, precision: None },
+            ("DOUBLE PRECISION", []) => Field::DoublePrecision,
+            ("SMALLSERIAL", []) => Field::SmallSerial,
+            ("BIGSERIAL", []) => Field::BigSerial,
+            ("SERIAL", []) => Field::Serial,
+            ("SMALLINT", []) => Field::SmallInt,
+            ("INTEGER", []) => Field::Integer,
+            ("BIGINT", []) => Field::BigInt,
+            ("REAL", []) => Field::Real,
+            ("MONEY", []) => Field::Money,
+            ("TEXT", []) => Field::Text,
+            ("BOOLEAN", []) => Field::Boolean,
+            ("DATE", []) => Field::Date,
+            ("UUID", []) => Field::Uuid,
+            ("JSONB", []) => Field::Jsonb,
+            ("JSON", []) => Field::Json,
+            ("BYTEA", []) => Field::Bytea,
+            ("INET", []) => Field::Inet,
+            ("CIDR", []) => Field::Cidr,
+            ("MACADDR", []) => Field::MacAddr,
+            _ => return Err(Error::Parse(format!("{} needs a length", type_name))),
+        };
+
+        Ok(self.array_suffix(kind))
+    }
+
+    fn array_suffix(&mut self, mut kind: Field) -> Field {
+        while self.rest.starts_with("[]") {
+            self.rest = &self.rest[2..];
+            kind = Field::Array(Box::new(kind));
+        }
+        kind
+    }
+
+    fn identifier(&mut self) -> Result<(String, bool), Error> {
+        self.skip_whitespace();
+        if let Some(quoted) = self.rest.strip_prefix('"') {
+            let mut name = String::new();
+            let mut chars = quoted.char_indices();
+            while let Some((index, c)) = chars.next() {
+                if c != '"' {
+                    name.push(c);
+                } else if quoted[index + 1..].starts_with('"') {
+                    name.push('"');
+                    chars.next();
+                } else {
+                    self.rest = &quoted[index + 1..];
+                    return Ok((name, true));
+                }
+            }
+            return Err(Error::Parse("unterminated quoted identifier".to_string()));
+        }
+
+        let end = self.rest.find(|c: char| !(c.is_alphanumeric() || c == '_')).unwrap_or(self.rest.len());
+        if end == 0 {
+            return Err(self.unexpected());
+        }
+        let (name, rest) = self.rest.split_at(end);
+        self.rest = rest;
+        Ok((name.to_string(), false))
+    }
+
+    fn identifier_list(&mut self) -> Result<Vec<String>, Error> {
+        self.expect_symbol("(")?;
+        let mut names = vec![self.identifier()?.0];
+        while self.symbol(",") {
+            names.push(self.identifier()?.0);
+        }
+        self.expect_symbol(")")?;
+        Ok(names)
+    }
+
+    fn number(&mut self) -> Result<usize, Error> {
+        self.skip_whitespace();
+        let end = self.rest.find(|c: char| !c.is_ascii_digit()).unwrap_or(self.rest.len());
+        let number = self.rest[..end].parse().map_err(|_| self.unexpected())?;
+        self.rest = &self.rest[end..];
+        Ok(number)
+    }
+
+    fn keyword(&mut self, keyword: &str) -> bool {
+        let saved = self.rest;
+        for word in keyword.split(' ') {
+            self.skip_whitespace();
+            let matches = self.rest.get(..word.len()).is_some_and(|prefix| prefix.eq_ignore_ascii_case(word))
+                && !self.rest[word.len()..].starts_with(|c: char| c.is_alphanumeric() || c == '_');
+            if !matches {
+                self.rest = saved;
+                return false;
+            }
+            self.rest = &self.rest[word.len()..];
+        }
+        true
+    }
+
+    fn expect_keyword(&mut self, keyword: &str) -> Result<(), Error> {
+        if self.keyword(keyword) {
+            Ok(())
+        } else {
+            Err(Error::Parse(format!("expected {}, found {:?}", keyword, self.next_token())))
+        }
+    }
+
+    fn precision(precision: usize) -> Result<u8, Error> {
+        u8::try_from(precision).map_err(|_| Error::Parse(format!("precision {} is out of range", precision)))
+    }
+
+    fn numeric(number: usize) -> Result<u32, Error> {
+        u32::try_from(number).map_err(|_| Error::Parse(format!("numeric precision or scale {} is out of range", number)))
+    }
+
+    fn symbol(&mut self, symbol: &str) -> bool {
+        self.skip_whitespace();
+        match self.rest.strip_prefix(symbol) {
+            Some(rest) => {
+                self.rest = rest;
+                true
+            },
+            None => false,
+        }
+    }
+
+    fn expect_symbol(&mut self, symbol: &str) -> Result<(), Error> {
+        if self.symbol(symbol) {
+            Ok(())
+        } else {
+            Err(Error::Parse(format!("expected {:?}, found {:?}", symbol, self.next_token())))
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        self.rest = self.rest.trim_start();
+    }
+
+    fn next_token(&self) -> &'a str {
+        let rest = self.rest.trim_start();
+        let end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+        &rest[..end]
+    }
+
+    fn unexpected(&self) -> Error {
+        Error::Parse(format!("unexpected {:?}", self.next_token()))
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use diesel::pg::Pg;
+
+    use super::*;
+    use crate::table::fields::IntoSql;
+
+    #[test]
+    fn posts_round_trip() {
+        let posts = TableDefn {
+            options: CommonTableOptions { name: "posts".to_string(), if_not_exists: true, kind: Some(TableKind::Global), ..Default::default() },
+            fields: vec![
+                TableField {
+                    options: CommonFieldOptions { name: "id".to_string(), primary_key: true, ..Default::default() },
+                    kind: Field::Serial
+                },
+                TableField {
+                    options: CommonFieldOptions { name: "title".to_string(), null: Some(false), ..Default::default() },
+                    kind: Field::Char { max_length: Some(10) }
+                },
+                TableField {
+                    options: CommonFieldOptions { name: "body".to_string(), null: Some(false), ..Default::default() },
+                    kind: Field::Text
+                },
+                TableField {
+                    options: CommonFieldOptions { name: "published".to_string(), null: Some(false), ..Default::default() },
+                    kind: Field::Boolean
+                },
+            ],
+        };
+        let (sql, _) = IntoSql::<Pg>::into_sql_str(&posts).unwrap();
+        let parsed = TableDefn::parse(&sql).unwrap();
+        assert_eq!(IntoSql::<Pg>::into_sql_str(&parsed).unwrap().0, sql);
+        assert_eq!(parsed.options.name, "posts");
+        assert!(parsed.options.if_not_exists);
+        assert_eq!(parsed.fields.len(), 4);
+    }
+
+    #[test]
+    fn parses_compound_types() {
+        let sql = "CREATE TABLE \"user\" (\n\t\"order\" NUMERIC(10, 2) NOT NULL UNIQUE,\n\ttags TEXT[] NULL,\n\tseen_at TIMESTAMP WITH TIME ZONE,\n\tPRIMARY KEY (\"order\", tags)\n)";
+        let parsed = TableDefn::parse(sql).unwrap();
+        assert!(parsed.options.quote_identifiers);
+        assert_eq!(parsed.options.primary_key, ["order", "tags"]);
+        assert_eq!(IntoSql::<Pg>::into_sql_str(&parsed).unwrap().0, sql);
+    }
+
+    #[test]
+    fn parses_datetime_precision() {
+        let sql = "CREATE TABLE events (\n\tcreated_at TIMESTAMP(3) WITH TIME ZONE NOT NULL,\n\tstarts TIME(0)\n)";
+        let parsed = TableDefn::parse(sql).unwrap();
+        assert!(matches!(parsed.fields[0].kind, Field::Timestamp { with_timezone: true, precision: Some(3) }));
+        assert_eq!(IntoSql::<Pg>::into_sql_str(&parsed).unwrap().0, sql);
+    }
+
+    #[test]
+    fn parses_interval_qualifiers() {
+        let sql = "CREATE TABLE jobs (\n\ttimeout INTERVAL DAY TO SECOND(3),\n\tperiod INTERVAL YEAR,\n\tevery INTERVAL\n)";
+        let parsed = TableDefn::parse(sql).unwrap();
+        assert_eq!(parsed.fields[0].kind, Field::Interval { fields: Some("DAY TO SECOND".to_string()), precision: Some(3) });
+        assert_eq!(IntoSql::<Pg>::into_sql_str(&parsed).unwrap().0, sql);
+    }
+
+    #[test]
+    fn unknown_types_are_enums() {
+        let sql = "CREATE TABLE posts (\n\tmood app.mood NOT NULL,\n\tstatus post_status\n)";
+        let parsed = TableDefn::parse(sql).unwrap();
+        assert_eq!(parsed.fields[0].kind, Field::Enum { type_name: "mood".to_string(), schema: Some("app".to_string()) });
+        assert_eq!(parsed.fields[1].kind, Field::Enum { type_name: "post_status".to_string(), schema: None });
+        assert_eq!(IntoSql::<Pg>::into_sql_str(&parsed).unwrap().0, sql);
+    }
+
+    #[test]
+    fn rejects_misspelled_types() {
+        for type_name in ["INTERGER", "TIMESTMP", "boolen", "txt"] {
+            let sql = format!("CREATE TABLE posts (\n\tcount {}\n)", type_name);
+            assert!(matches!(TableDefn::parse(&sql), Err(Error::Parse(_))), "{}", type_name);
+        }
+    }
+
+    #[test]
+    fn rejects_out_of_range_numeric() {
+        let sql = "CREATE TABLE prices (\n\tamount NUMERIC(4294967296, 2)\n)";
+        assert!(matches!(TableDefn::parse(sql), Err(Error::Parse(_))));
+        let sql = "CREATE TABLE prices (\n\tamount NUMERIC(10, 4294967296)\n)";
+        assert!(matches!(TableDefn::parse(sql), Err(Error::Parse(_))));
+    }
+
+    #[test]
+    fn rejects_unsupported_syntax() {
+        let sql = "CREATE TABLE posts (\n\tid SERIAL DEFAULT 1\n)";
+        assert!(matches!(TableDefn::parse(sql), Err(Error::Parse(_))));
+        assert!(matches!(TableDefn::parse("DROP TABLE posts"), Err(Error::Parse(_))));
+    }
+}
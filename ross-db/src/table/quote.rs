@@ -0,0 +1,69 @@
+use std::borrow::Cow;
+
+
+const RESERVED_KEYWORDS: &[&str] = &[
+    "all", "analyse", "analyze", "and", "any", "array", "as", "asc", "asymmetric",
+    "authorization", "binary", "both", "case", "cast", "check", "collate", "collation",
+    "column", "concurrently", "constraint", "create", "cross", "current_catalog",
+    "current_date", "current_role", "current_schema", "current_time", "current_timestamp",
+    "current_user", "default", "deferrable", "desc", "distinct", "do", "else", "end",
+    "except", "false", "fetch", "for", "foreign", "freeze", "from", "full", "grant",
+    "group", "having", "ilike", "in", "initially", "inner", "intersect", "into", "is",
+    "isnull", "join", "lateral", "leading", "left", "like", "limit", "localtime",
+    "localtimestamp", "natural", "not", "notnull", "null", "offset", "on", "only", "or",
+    "order", "outer", "overlaps", "placing", "primary", "references", "returning", "right",
+    "select", "session_user", "similar", "some", "symmetric", "system_user", "table",
+    "tablesample", "then", "to", "trailing", "true", "union", "unique", "user", "using",
+    "variadic", "verbose", "when", "where", "window", "with",
+];
+
+
+fn needs_quoting(name: &str) -> bool {
+    let starts_well = name
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_lowercase() || c == '_');
+    let rest_is_plain = name
+        .chars()
+        .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_');
+
+    !starts_well || !rest_is_plain || RESERVED_KEYWORDS.contains(&name)
+}
+
+pub(crate) fn identifier(name: &str, quote: bool) -> Cow<'_, str> {
+    if quote && needs_quoting(name) {
+        Cow::Owned(format!("\"{}\"", name.replace('"', "\"\"")))
+    } else {
+        Cow::Borrowed(name)
+    }
+}
+
+pub(crate) fn literal(text: &str) -> String {
+    format!("'{}'", text.replace('\'', "''"))
+}
+
+pub(crate) fn literal_list(values: &[String]) -> String {
+    values.iter().map(|value| literal(value)).collect::<Vec<_>>().join(", ")
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn literal_escapes_quotes() {
+        assert_eq!(literal("it's"), "'it''s'");
+        assert_eq!(literal(""), "''");
+    }
+
+    #[test]
+    fn identifier_quoting() {
+        assert_eq!(identifier("order", false), "order");
+        assert_eq!(identifier("order", true), "\"order\"");
+        assert_eq!(identifier("title", true), "title");
+        assert_eq!(identifier("createdAt", true), "\"createdAt\"");
+        assert_eq!(identifier("2fa", true), "\"2fa\"");
+        assert_eq!(identifier("say \"hi\"", true), "\"say \"\"hi\"\"\"");
+    }
+}
@@ -0,0 +1,91 @@
+use std::fmt;
+
+use diesel::pg::Pg;
+
+use crate::Error;
+use crate::table::fields::{IntoSql, SqlWrite};
+use crate::table::quote;
+
+
+/// A `CREATE SEQUENCE` statement, for sequences managed apart from serial columns.
+#[derive(Debug, Clone, Default)]
+pub struct Sequence {
+    pub name: String,
+    pub start: Option<i64>,
+    pub increment: Option<i64>,
+    /// The `(table, column)` the sequence is dropped together with.
+    pub owned_by: Option<(String, String)>,
+    /// Double-quote names that are reserved words or contain special characters.
+    pub quote_identifiers: bool,
+}
+
+impl IntoSql<Pg> for Sequence {
+    fn into_sql_fmt(&self, writer: &mut dyn fmt::Write) -> Result<usize, Error> {
+        if self.increment == Some(0) {
+            return Err(Error::InvalidSchema(format!("sequence {} cannot increment by 0", self.name)));
+        }
+
+        let quote = |name: &str| quote::identifier(name, self.quote_identifiers).into_owned();
+
+        let mut total_bytes = 0;
+        total_bytes += writer.write_sql("CREATE SEQUENCE ")?;
+        total_bytes += writer.write_sql(&quote(&self.name))?;
+        if let Some(start) = self.start {
+            total_bytes += writer.write_sql_fmt(format_args!(" START WITH {}", start))?;
+        }
+        if let Some(increment) = self.increment {
+            total_bytes += writer.write_sql_fmt(format_args!(" INCREMENT BY {}", increment))?;
+        }
+        if let Some((table, column)) = &self.owned_by {
+            total_bytes += writer.write_sql(" OWNED BY ")?;
+            total_bytes += writer.write_sql(&quote(table))?;
+            total_bytes += writer.write_sql(".")?;
+            total_bytes += writer.write_sql(&quote(column))?;
+        }
+        Ok(total_bytes)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn minimal_sequence() {
+        let sequence = Sequence { name: "invoice_numbers".to_string(), ..Default::default() };
+        let (observed, _) = IntoSql::<Pg>::into_sql_str(&sequence).unwrap();
+        assert_eq!(observed, "CREATE SEQUENCE invoice_numbers");
+    }
+
+    #[test]
+    fn full_sequence() {
+        let sequence = Sequence {
+            name: "invoice_numbers".to_string(),
+            start: Some(1000),
+            increment: Some(-5),
+            owned_by: Some(("invoices".to_string(), "number".to_string())),
+            quote_identifiers: false,
+        };
+        let (observed, bytes_written) = IntoSql::<Pg>::into_sql_str(&sequence).unwrap();
+        assert_eq!(observed, "CREATE SEQUENCE invoice_numbers START WITH 1000 INCREMENT BY -5 OWNED BY invoices.number");
+        assert_eq!(bytes_written, observed.len());
+    }
+    #[test]
+    fn quoted_sequence() {
+        let sequence = Sequence {
+            name: "Order numbers".to_string(),
+            owned_by: Some(("order".to_string(), "number".to_string())),
+            quote_identifiers: true,
+            ..Default::default()
+        };
+        let (observed, _) = IntoSql::<Pg>::into_sql_str(&sequence).unwrap();
+        assert_eq!(observed, "CREATE SEQUENCE \"Order numbers\" OWNED BY \"order\".number");
+    }
+
+    #[test]
+    fn zero_increment() {
+        let sequence = Sequence { name: "invoice_numbers".to_string(), increment: Some(0), ..Default::default() };
+        assert!(matches!(IntoSql::<Pg>::into_sql_str(&sequence), Err(Error::InvalidSchema(_))));
+    }
+}
@@ -0,0 +1,404 @@
+use std::fmt;
+
+use diesel::sqlite::Sqlite;
+
+use crate::Error;
+use crate::table::fields::{Field, IntoSql, SqlWrite, TableDefn, TableField};
+use crate::table::format::FormatOptions;
+use crate::table::insert::InsertPlaceholders;
+use crate::table::quote;
+
+
+impl IntoSql<Sqlite> for Field {
+    fn into_sql_fmt(&self, writer: &mut dyn fmt::Write) -> Result<usize, Error> {
+        self.validate()?;
+        let data_type = match self {
+            Self::Char { .. } | Self::VarChar { .. } | Self::Text => "TEXT",
+            Self::SmallSerial | Self::Serial | Self::BigSerial => "INTEGER",
+            Self::SmallInt | Self::Integer | Self::BigInt => "INTEGER",
+            Self::Real | Self::DoublePrecision => "REAL",
+            Self::Numeric { .. } | Self::Money => "NUMERIC",
+            Self::Boolean => "BOOLEAN",
+            Self::Date | Self::Time { .. } | Self::Timestamp { .. } | Self::Interval { .. } => "TEXT",
+            Self::Uuid | Self::Json | Self::Jsonb => "TEXT",
+            Self::Inet | Self::Cidr | Self::MacAddr => "TEXT",
+            Self::Enum { .. } => "TEXT",
+            Self::Bytea => "BLOB",
+            Self::Bit { .. } | Self::BitVarying { .. } => "INTEGER",
+            Self::Array(_) => {
+                return Err(Error::InvalidSchema(format!("{:?} columns are not supported by SQLite", self)));
+            },
+        };
+
+        writer
+        .write_sql(data_type)
+    }
+}
+
+impl TableField {
+    fn write_sqlite(&self, writer: &mut dyn fmt::Write, inline_primary_key: bool, auto_increment: bool, quote_identifiers: bool) -> Result<usize, Error> {
+        self.validate()?;
+        if self.options.identity.is_some() {
+            return Err(Error::InvalidSchema(format!(
+                "identity column {} is not supported by SQLite, use a serial primary key instead",
+                self.options.name
+            )));
+        }
+        // Anywhere but the rowid alias a serial would silently lose its auto-increment.
+        if self.kind.is_serial() && !auto_increment {
+            return Err(Error::InvalidSchema(format!(
+                "serial column {} must be the only primary key column on SQLite",
+                self.options.name
+            )));
+        }
+
+        let quote = |name: &str| quote::identifier(name, quote_identifiers).into_owned();
+
+        let mut total_bytes = 0;
+        total_bytes += writer.write_sql(&quote(&self.options.name))?;
+        total_bytes += writer.write_sql(" ")?;
+
+        total_bytes += IntoSql::<Sqlite>::into_sql_fmt(&self.kind, writer)?;
+
+        if let Some(collation) = &self.options.collation {
+            total_bytes += writer.write_sql(" COLLATE ")?;
+            total_bytes += writer.write_sql(collation)?;
+        }
+
+        if let Some(generated) = &self.options.generated {
+            total_bytes += writer.write_sql(" ")?;
+            total_bytes += generated.write_pg(writer, &FormatOptions::default())?;
+        }
+
+        if let Some(default) = &self.options.default {
+            total_bytes += writer.write_sql(" DEFAULT ")?;
+            total_bytes += writer.write_sql(default)?;
+        }
+
+        let mut constraints = Vec::new();
+        if let Some(null_constraint) = self.options.null {
+            constraints.push(if null_constraint {
+                "NULL"
+            } else {
+                "NOT NULL"
+            }.to_string());
+        }
+        if auto_increment || (self.options.primary_key && inline_primary_key) {
+            if let Some(name) = &self.options.primary_key_constraint {
+                constraints.push(format!("CONSTRAINT {}", quote(name)));
+            }
+            constraints.push(if auto_increment {
+                "PRIMARY KEY AUTOINCREMENT"
+            } else {
+                "PRIMARY KEY"
+            }.to_string());
+        }
+        if self.options.unique {
+            if let Some(name) = &self.options.unique_constraint {
+                constraints.push(format!("CONSTRAINT {}", quote(name)));
+            }
+            constraints.push("UNIQUE".to_string());
+        }
+
+        if !constraints.is_empty() {
+            total_bytes += writer.write_sql(" ")?;
+            total_bytes += writer.write_sql(&constraints.join(" "))?;
+        }
+
+        if let Some(check) = &self.options.check {
+            total_bytes += writer.write_sql(" ")?;
+            total_bytes += write_check(writer, self.options.check_constraint.as_deref(), check, &quote)?;
+        }
+
+        if let Some(values) = &self.options.allowed_values {
+            let check = format!("{} IN ({})", quote(&self.options.name), quote::literal_list(values));
+            total_bytes += writer.write_sql(" ")?;
+            total_bytes += write_check(writer, None, &check, &quote)?;
+        }
+
+        if let Some(references) = &self.options.references {
+            total_bytes += writer.write_sql(" ")?;
+            total_bytes += references.write_with(writer, &FormatOptions::default(), quote)?;
+        }
+
+        Ok(total_bytes)
+    }
+}
+
+impl IntoSql<Sqlite> for TableField {
+    fn into_sql_fmt(&self, writer: &mut dyn fmt::Write) -> Result<usize, Error> {
+        self.write_sqlite(writer, true, self.options.primary_key && self.kind.is_serial(), false)
+    }
+}
+
+fn write_check(writer: &mut dyn fmt::Write, name: Option<&str>, check: &str, quote: &impl Fn(&str) -> String) -> Result<usize, Error> {
+    let mut total_bytes = 0;
+    if let Some(name) = name {
+        total_bytes += writer.write_sql("CONSTRAINT ")?;
+        total_bytes += writer.write_sql(&quote(name))?;
+        total_bytes += writer.write_sql(" ")?;
+    }
+    total_bytes += writer.write_sql("CHECK (")?;
+    total_bytes += writer.write_sql(check)?;
+    total_bytes += writer.write_sql(")")?;
+    Ok(total_bytes)
+}
+
+impl IntoSql<Sqlite> for TableDefn {
+    fn into_sql_fmt(&self, writer: &mut dyn fmt::Write) -> Result<usize, Error> {
+        self.validate()?;
+        if !self.options.exclusions.is_empty() {
+            return Err(Error::InvalidSchema(format!("table {} has EXCLUDE constraints, which SQLite does not support", self.options.name)));
+        }
+
+        let quote = |name: &str| quote::identifier(name, self.options.quote_identifiers).into_owned();
+        let column_list = |columns: &[String]| columns.iter().map(|column| quote(column)).collect::<Vec<_>>().join(", ");
+
+        // `TableKind` has no SQLite equivalent, so it is ignored here.
+        let mut total_bytes = 0;
+        total_bytes += writer.write_sql("CREATE TABLE ")?;
+        if self.options.if_not_exists {
+            total_bytes += writer.write_sql("IF NOT EXISTS ")?;
+        }
+        total_bytes += writer.write_sql(&self.quoted_name())?;
+
+        total_bytes += writer.write_sql(" (\n\t")?;
+
+        // SQLite has no serial types; AUTOINCREMENT only works on a column that alone is the
+        // primary key, and only inline, so such a key never becomes a table constraint.
+        let auto_increment = self
+            .sole_primary_key()
+            .filter(|name| self.fields.iter().any(|field| field.options.name == *name && field.kind.is_serial()));
+        let inline_primary_key = self.options.primary_key.is_empty();
+
+        for (index, field) in self.fields.iter().enumerate() {
+            if index > 0 {
+                total_bytes += writer.write_sql(",\n\t")?;
+            }
+            let auto_increment = auto_increment == Some(&field.options.name);
+            total_bytes += field.write_sqlite(writer, inline_primary_key, auto_increment, self.options.quote_identifiers)?;
+        }
+
+        if !inline_primary_key && auto_increment.is_none() {
+            total_bytes += writer.write_sql(",\n\tPRIMARY KEY (")?;
+            total_bytes += writer.write_sql(&column_list(&self.options.primary_key))?;
+            total_bytes += writer.write_sql(")")?;
+        }
+
+        for (name, columns) in &self.options.unique_constraints {
+            total_bytes += writer.write_sql(",\n\t")?;
+            if let Some(name) = name {
+                total_bytes += writer.write_sql("CONSTRAINT ")?;
+                total_bytes += writer.write_sql(&quote(name))?;
+                total_bytes += writer.write_sql(" ")?;
+            }
+            total_bytes += writer.write_sql("UNIQUE (")?;
+            total_bytes += writer.write_sql(&column_list(columns))?;
+            total_bytes += writer.write_sql(")")?;
+        }
+
+        for foreign_key in &self.options.foreign_keys {
+            total_bytes += writer.write_sql(",\n\t")?;
+            total_bytes += foreign_key.write_with(writer, &FormatOptions::default(), quote)?;
+        }
+
+        for (name, check) in &self.options.checks {
+            total_bytes += writer.write_sql(",\n\t")?;
+            total_bytes += write_check(writer, name.as_deref(), check, &quote)?;
+        }
+
+        total_bytes += writer.write_sql("\n)")?;
+        Ok(total_bytes)
+    }
+}
+
+impl IntoSql<Sqlite> for InsertPlaceholders {
+    fn into_sql_fmt(&self, writer: &mut dyn fmt::Write) -> Result<usize, Error> {
+        self.write_with(
+            writer,
+            |name| quote::identifier(name, self.quote_identifiers).into_owned(),
+            |_| "?".to_string(),
+        )
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use diesel::pg::Pg;
+
+    use super::*;
+    use crate::table::fields::{CommonFieldOptions, CommonTableOptions, TableKind};
+
+    #[test]
+    fn table_differs_from_pg() {
+        let posts = TableDefn {
+            options: CommonTableOptions { name: "posts".to_string(), if_not_exists: true, kind: Some(TableKind::Local), ..Default::default() },
+            fields: vec![
+                TableField {
+                    options: CommonFieldOptions {
+                        name: "id".to_string(),
+                        primary_key: true,
+                        unique: false,
+                        null: None,
+                        ..Default::default()
+                    },
+                    kind: Field::BigSerial
+                },
+                TableField {
+                    options: CommonFieldOptions {
+                        name: "title".to_string(),
+                        primary_key: false,
+                        unique: true,
+                        null: Some(false),
+                        ..Default::default()
+                    },
+                    kind: Field::VarChar { max_length: Some(80) }
+                },
+                TableField {
+                    options: CommonFieldOptions {
+                        name: "created_at".to_string(),
+                        primary_key: false,
+                        unique: false,
+                        null: Some(false),
+                        ..Default::default()
+                    },
+                    kind: Field::Timestamp { with_timezone: true, precision: None }
+                },
+            ],
+        };
+
+        let (observed, _) = IntoSql::<Sqlite>::into_sql_str(&posts).unwrap();
+        let expected = "CREATE TABLE IF NOT EXISTS posts (\n\tid INTEGER PRIMARY KEY AUTOINCREMENT,\n\ttitle TEXT NOT NULL UNIQUE,\n\tcreated_at TEXT NOT NULL\n)";
+        assert_eq!(observed, expected);
+
+        let (observed, _) = IntoSql::<Pg>::into_sql_str(&posts).unwrap();
+        let expected = "CREATE LOCAL TEMPORARY TABLE IF NOT EXISTS posts (\n\tid BIGSERIAL PRIMARY KEY,\n\ttitle VARCHAR(80) NOT NULL UNIQUE,\n\tcreated_at TIMESTAMP WITH TIME ZONE NOT NULL\n)";
+        assert_eq!(observed, expected);
+    }
+
+    #[test]
+    fn composite_primary_key() {
+        let memberships = TableDefn::builder("memberships")
+            .column("user_id", Field::Integer, |c| c.primary_key())
+            .column("group_id", Field::Integer, |c| c.primary_key())
+            .primary_key(["user_id", "group_id"])
+            .build();
+        let (observed, _) = IntoSql::<Sqlite>::into_sql_str(&memberships).unwrap();
+        let expected = "CREATE TABLE memberships (\n\tuser_id INTEGER,\n\tgroup_id INTEGER,\n\tPRIMARY KEY (user_id, group_id)\n)";
+        assert_eq!(observed, expected);
+    }
+
+    #[test]
+    fn serial_outside_the_primary_key() {
+        let memberships = TableDefn::builder("memberships")
+            .column("user_id", Field::Integer, |c| c)
+            .column("position", Field::Serial, |c| c)
+            .primary_key(["user_id"])
+            .build();
+        assert!(matches!(IntoSql::<Sqlite>::into_sql_str(&memberships), Err(Error::InvalidSchema(_))));
+
+        let position = TableField::new("position", Field::BigSerial);
+        assert!(matches!(IntoSql::<Sqlite>::into_sql_str(&position), Err(Error::InvalidSchema(_))));
+    }
+
+    #[test]
+    fn table_level_serial_key_stays_inline() {
+        let posts = TableDefn::builder("posts")
+            .column("id", Field::BigSerial, |c| c)
+            .column("title", Field::Text, |c| c)
+            .primary_key(["id"])
+            .build();
+        let (observed, _) = IntoSql::<Sqlite>::into_sql_str(&posts).unwrap();
+        assert_eq!(observed, "CREATE TABLE posts (\n\tid INTEGER PRIMARY KEY AUTOINCREMENT,\n\ttitle TEXT\n)");
+    }
+
+    #[test]
+    fn constraints() {
+        use crate::table::constraints::{Deferrable, ReferentialAction, TableForeignKey};
+
+        let mut orders = TableDefn::builder("orders")
+            .quote_identifiers()
+            .column("id", Field::Serial, |c| c.primary_key())
+            .column("user", Field::Integer, |c| {
+                c.not_null().references("users", "id").references.as_mut().unwrap().on_delete = Some(ReferentialAction::Cascade);
+                c
+            })
+            .column("code", Field::Text, |c| c.collation("NOCASE").unique_named("orders_code_key"))
+            .column("quantity", Field::Integer, |c| c.default_value("1").check("quantity > 0"))
+            .column("region", Field::Text, |c| c)
+            .column("total", Field::Integer, |c| c.generated("quantity * 2", true))
+            .unique(Some("orders_region_key"), ["region", "code"])
+            .check(Some("orders_total_check"), "total >= quantity")
+            .build();
+        orders.options.foreign_keys.push(TableForeignKey {
+            columns: vec!["region".to_string()],
+            table: "regions".to_string(),
+            referenced_columns: vec!["name".to_string()],
+            deferrable: Some(Deferrable { deferrable: true, initially_deferred: true }),
+            ..Default::default()
+        });
+        let (observed, _) = IntoSql::<Sqlite>::into_sql_str(&orders).unwrap();
+        let expected = concat!(
+            "CREATE TABLE orders (\n",
+            "\tid INTEGER PRIMARY KEY AUTOINCREMENT,\n",
+            "\t\"user\" INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,\n",
+            "\tcode TEXT COLLATE NOCASE CONSTRAINT orders_code_key UNIQUE,\n",
+            "\tquantity INTEGER DEFAULT 1 CHECK (quantity > 0),\n",
+            "\tregion TEXT,\n",
+            "\ttotal INTEGER GENERATED ALWAYS AS (quantity * 2) STORED,\n",
+            "\tCONSTRAINT orders_region_key UNIQUE (region, code),\n",
+            "\tFOREIGN KEY (region) REFERENCES regions (name) DEFERRABLE INITIALLY DEFERRED,\n",
+            "\tCONSTRAINT orders_total_check CHECK (total >= quantity)\n",
+            ")",
+        );
+        assert_eq!(observed, expected);
+    }
+
+    #[test]
+    fn inexpressible_options() {
+        use crate::table::constraints::ExcludeConstraint;
+        use crate::table::fields::IdentityKind;
+        use crate::table::index::IndexMethod;
+
+        let identity = TableDefn::builder("orders")
+            .column("id", Field::BigInt, |c| c.identity(IdentityKind::ByDefault))
+            .build();
+        assert!(matches!(IntoSql::<Sqlite>::into_sql_str(&identity), Err(Error::InvalidSchema(_))));
+
+        let mut bookings = TableDefn::builder("bookings").column("room", Field::Integer, |c| c).build();
+        bookings.options.exclusions.push(ExcludeConstraint {
+            name: None,
+            method: IndexMethod::Gist,
+            elements: vec![("room".to_string(), "=".to_string())],
+        });
+        assert!(matches!(IntoSql::<Sqlite>::into_sql_str(&bookings), Err(Error::InvalidSchema(_))));
+    }
+
+    #[test]
+    fn quoted_placeholders() {
+        let orders = TableDefn::builder("orders")
+            .quote_identifiers()
+            .column("id", Field::Serial, |c| c.primary_key())
+            .column("order", Field::Integer, |c| c)
+            .build();
+        let observed = orders.insert_placeholders::<Sqlite>(&["order"]).unwrap();
+        assert_eq!(observed, "INSERT INTO orders (\"order\") VALUES (?)");
+    }
+
+    #[test]
+    fn invalid_field() {
+        assert!(matches!(
+            IntoSql::<Sqlite>::into_sql_str(&Field::Char { max_length: Some(0) }),
+            Err(Error::InvalidSchema(_))
+        ));
+    }
+
+    #[test]
+    fn text_like_fields_collapse_to_text() {
+        for field in [Field::Char { max_length: Some(3) }, Field::VarChar { max_length: None }, Field::Text] {
+            let (observed, _) = IntoSql::<Sqlite>::into_sql_str(&field).unwrap();
+            assert_eq!(observed, "TEXT");
+        }
+    }
+}
@@ -0,0 +1,75 @@
+use std::fmt;
+
+use diesel::pg::Pg;
+
+use crate::Error;
+use crate::table::fields::{IntoSql, SqlWrite};
+
+
+/// A `CREATE VIEW` statement; the query is written verbatim.
+#[derive(Debug, Clone, Default)]
+pub struct View {
+    pub name: String,
+    pub query: String,
+    pub or_replace: bool,
+    pub materialized: bool,
+}
+
+impl IntoSql<Pg> for View {
+    fn into_sql_fmt(&self, writer: &mut dyn fmt::Write) -> Result<usize, Error> {
+        if self.or_replace && self.materialized {
+            return Err(Error::InvalidSchema(format!("materialized view {} cannot be created with OR REPLACE", self.name)));
+        }
+
+        let mut total_bytes = 0;
+        total_bytes += writer.write_sql("CREATE ")?;
+        if self.or_replace {
+            total_bytes += writer.write_sql("OR REPLACE ")?;
+        }
+        if self.materialized {
+            total_bytes += writer.write_sql("MATERIALIZED ")?;
+        }
+        total_bytes += writer.write_sql("VIEW ")?;
+        total_bytes += writer.write_sql(&self.name)?;
+        total_bytes += writer.write_sql(" AS ")?;
+        total_bytes += writer.write_sql(&self.query)?;
+        Ok(total_bytes)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn published_posts() -> View {
+        View {
+            name: "published_posts".to_string(),
+            query: "SELECT * FROM posts WHERE published".to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn plain_view() {
+        let (observed, _) = IntoSql::<Pg>::into_sql_str(&published_posts()).unwrap();
+        assert_eq!(observed, "CREATE VIEW published_posts AS SELECT * FROM posts WHERE published");
+    }
+
+    #[test]
+    fn or_replace_view() {
+        let view = View { or_replace: true, ..published_posts() };
+        let (observed, _) = IntoSql::<Pg>::into_sql_str(&view).unwrap();
+        assert_eq!(observed, "CREATE OR REPLACE VIEW published_posts AS SELECT * FROM posts WHERE published");
+    }
+
+    #[test]
+    fn materialized_view() {
+        let view = View { materialized: true, ..published_posts() };
+        let (observed, _) = IntoSql::<Pg>::into_sql_str(&view).unwrap();
+        assert_eq!(observed, "CREATE MATERIALIZED VIEW published_posts AS SELECT * FROM posts WHERE published");
+
+        let view = View { or_replace: true, ..view };
+        assert!(matches!(IntoSql::<Pg>::into_sql_str(&view), Err(Error::InvalidSchema(_))));
+    }
+}
@@ -0,0 +1,23 @@
+//! Runs generated SQL against a live Postgres.
+//!
+//! Ignored by default, run with `DATABASE_URL=postgres://... cargo test -- --ignored`.
+
+use diesel::pg::PgConnection;
+use diesel::{Connection, RunQueryDsl};
+use ross_db::table::fields::{Field, TableDefn};
+
+
+#[test]
+#[ignore]
+fn create_and_drop_table() {
+    let database_url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+    let mut conn = PgConnection::establish(&database_url).unwrap();
+
+    let table = TableDefn::builder("ross_execute_smoke")
+        .column("id", Field::Serial, |c| c.primary_key())
+        .column("title", Field::Text, |c| c.not_null())
+        .build();
+    table.execute(&mut conn).unwrap();
+
+    diesel::sql_query("DROP TABLE ross_execute_smoke").execute(&mut conn).unwrap();
+}
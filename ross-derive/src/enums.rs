@@ -0,0 +1,42 @@
+use quote::quote;
+use syn::spanned::Spanned;
+
+use crate::table::{RenameRule, TableAttributes, to_snake_case};
+
+
+/// `#[derive(Table)]` on a unit-only enum describes a Postgres enum type whose labels
+/// are the variant names, snake_cased unless `#[table(rename_all = "...")]` picks another case.
+pub fn expand_enum_derive(input: &syn::DeriveInput, data: &syn::DataEnum) -> Result<proc_macro2::TokenStream, Vec<syn::Error>> {
+    let errors: Vec<syn::Error> = data
+        .variants
+        .iter()
+        .filter(|variant| !matches!(variant.fields, syn::Fields::Unit))
+        .map(|variant| syn::Error::new(variant.span(), "only unit variants can be turned into enum labels"))
+        .collect();
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+
+    let attributes = TableAttributes::from_input(input).map_err(|err| vec![err])?;
+    if attributes.if_not_exists || attributes.kind.is_some() {
+        return Err(vec![syn::Error::new(input.span(), "enum types only support the `name` and `rename_all` table attributes")]);
+    }
+
+    let ident = &input.ident;
+    let type_name = match attributes.name {
+        Some(name) => name.value(),
+        None => to_snake_case(&ident.to_string()),
+    };
+    let rename_all = attributes.rename_all.unwrap_or(RenameRule::Snake);
+    let labels = data.variants.iter().map(|variant| rename_all.apply(&variant.ident.to_string()));
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics #ident #ty_generics #where_clause {
+            /// The `CREATE TYPE ... AS ENUM` described by this enum's variants.
+            pub fn enum_type() -> ::ross_db::table::enums::EnumType {
+                ::ross_db::table::enums::EnumType::new(#type_name, [#(#labels),*])
+            }
+        }
+    })
+}
@@ -1,3 +1,4 @@
+mod enums;
 mod table;
 
 use proc_macro::TokenStream;
@@ -5,7 +6,7 @@ use syn::{DeriveInput, parse_macro_input};
 use quote::quote;
 
 
-#[proc_macro_derive(Table, attributes(field))]
+#[proc_macro_derive(Table, attributes(table, field))]
 pub fn derive_table(input: TokenStream) -> TokenStream {
     let mut input = parse_macro_input!(input as DeriveInput);
     crate::table::expand_table_derive(&mut input)
@@ -1,4 +1,367 @@
+use quote::quote;
+use syn::ext::IdentExt;
+use syn::spanned::Spanned;
+
+
+/// The options collected from a `#[field(...)]` attribute.
+#[derive(Default)]
+struct FieldAttributes {
+    name: Option<syn::LitStr>,
+    kind: Option<syn::Expr>,
+    null: Option<syn::LitBool>,
+    primary_key: Option<syn::Path>,
+    unique: bool,
+    skip: Option<syn::Path>,
+}
+
+impl FieldAttributes {
+    fn from_field(field: &syn::Field) -> Result<Self, syn::Error> {
+        let mut attributes = Self::default();
+
+        for attr in field.attrs.iter().filter(|attr| attr.path().is_ident("field")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("name") {
+                    attributes.name = Some(meta.value()?.parse()?);
+                    Ok(())
+                } else if meta.path.is_ident("kind") {
+                    attributes.kind = Some(meta.value()?.parse()?);
+                    Ok(())
+                } else if meta.path.is_ident("null") {
+                    attributes.null = Some(meta.value()?.parse()?);
+                    Ok(())
+                } else if meta.path.is_ident("primary_key") {
+                    attributes.primary_key = Some(meta.path);
+                    Ok(())
+                } else if meta.path.is_ident("unique") {
+                    attributes.unique = true;
+                    Ok(())
+                } else if meta.path.is_ident("skip") {
+                    attributes.skip = Some(meta.path);
+                    Ok(())
+                } else {
+                    Err(meta.error("unsupported field attribute, expected one of `name`, `kind`, `null`, `primary_key`, `unique`, `skip`"))
+                }
+            })?;
+        }
+
+        if let Some(skip) = &attributes.skip {
+            let configured = attributes.name.is_some()
+                || attributes.kind.is_some()
+                || attributes.null.is_some()
+                || attributes.primary_key.is_some()
+                || attributes.unique;
+            if configured {
+                return Err(syn::Error::new(skip.span(), "a skipped field cannot have other #[field(...)] options"));
+            }
+        }
+
+        Ok(attributes)
+    }
+}
+
+
+/// The `T` in `Option<T>`, if the type is an `Option` at all.
+fn option_inner_type(ty: &syn::Type) -> Option<&syn::Type> {
+    let segment = match ty {
+        syn::Type::Path(syn::TypePath { qself: None, path }) => path.segments.last()?,
+        _ => return None,
+    };
+    if segment.ident != "Option" {
+        return None;
+    }
+    match &segment.arguments {
+        syn::PathArguments::AngleBracketed(args) => match args.args.first() {
+            Some(syn::GenericArgument::Type(inner)) => Some(inner),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+
+/// Pick a default `Field` for a Rust type, used when `kind` is not given explicitly.
+fn infer_kind(ty: &syn::Type) -> Result<proc_macro2::TokenStream, syn::Error> {
+    let unsupported = || syn::Error::new(
+        ty.span(),
+        "cannot infer a column type for this field, specify it with #[field(kind = ...)]",
+    );
+
+    let segment = match ty {
+        syn::Type::Path(syn::TypePath { qself: None, path }) => path.segments.last().ok_or_else(unsupported)?,
+        _ => return Err(unsupported()),
+    };
+
+    let kind = match segment.ident.to_string().as_str() {
+        "String" => quote!(Text),
+        "bool" => quote!(Boolean),
+        "i16" => quote!(SmallInt),
+        "i32" => quote!(Integer),
+        "i64" => quote!(BigInt),
+        "f32" => quote!(Real),
+        "f64" => quote!(DoublePrecision),
+        "Vec" => {
+            let inner = match &segment.arguments {
+                syn::PathArguments::AngleBracketed(args) => match args.args.first() {
+                    Some(syn::GenericArgument::Type(inner)) => inner,
+                    _ => return Err(unsupported()),
+                },
+                _ => return Err(unsupported()),
+            };
+            if matches!(inner, syn::Type::Path(path) if path.path.is_ident("u8")) {
+                quote!(Bytea)
+            } else {
+                let inner = infer_kind(inner)?;
+                quote!(Array(::std::boxed::Box::new(#inner)))
+            }
+        },
+        _ => return Err(unsupported()),
+    };
+
+    Ok(quote!(::ross_db::table::fields::Field::#kind))
+}
+
+
+/// A naming convention applied by `#[table(rename_all = "...")]`.
+#[derive(Clone, Copy)]
+pub(crate) enum RenameRule {
+    Snake,
+    Camel,
+    ScreamingSnake,
+}
+
+impl RenameRule {
+    fn from_lit(lit: &syn::LitStr) -> Result<Self, syn::Error> {
+        match lit.value().as_str() {
+            "snake_case" => Ok(Self::Snake),
+            "camelCase" => Ok(Self::Camel),
+            "SCREAMING_SNAKE_CASE" => Ok(Self::ScreamingSnake),
+            _ => Err(syn::Error::new(lit.span(), "expected one of `snake_case`, `camelCase`, `SCREAMING_SNAKE_CASE`")),
+        }
+    }
+
+    pub(crate) fn apply(self, ident: &str) -> String {
+        let snake = to_snake_case(ident);
+        match self {
+            Self::Snake => snake,
+            Self::ScreamingSnake => snake.to_uppercase(),
+            Self::Camel => {
+                let mut camel = String::with_capacity(snake.len());
+                let mut upper_next = false;
+                for c in snake.chars() {
+                    if c == '_' {
+                        upper_next = !camel.is_empty();
+                    } else if upper_next {
+                        camel.extend(c.to_uppercase());
+                        upper_next = false;
+                    } else {
+                        camel.push(c);
+                    }
+                }
+                camel
+            },
+        }
+    }
+}
+
+
+/// The options collected from a struct-level `#[table(...)]` attribute.
+#[derive(Default)]
+pub(crate) struct TableAttributes {
+    pub(crate) name: Option<syn::LitStr>,
+    pub(crate) if_not_exists: bool,
+    pub(crate) kind: Option<syn::Ident>,
+    pub(crate) rename_all: Option<RenameRule>,
+}
+
+impl TableAttributes {
+    pub(crate) fn from_input(input: &syn::DeriveInput) -> Result<Self, syn::Error> {
+        let mut attributes = Self::default();
+
+        for attr in input.attrs.iter().filter(|attr| attr.path().is_ident("table")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("name") {
+                    attributes.name = Some(meta.value()?.parse()?);
+                    Ok(())
+                } else if meta.path.is_ident("if_not_exists") {
+                    attributes.if_not_exists = true;
+                    Ok(())
+                } else if meta.path.is_ident("kind") {
+                    let kind: syn::Ident = meta.value()?.parse()?;
+                    if kind != "Global" && kind != "Local" {
+                        return Err(syn::Error::new(kind.span(), "expected `Global` or `Local`"));
+                    }
+                    attributes.kind = Some(kind);
+                    Ok(())
+                } else if meta.path.is_ident("rename_all") {
+                    attributes.rename_all = Some(RenameRule::from_lit(&meta.value()?.parse()?)?);
+                    Ok(())
+                } else {
+                    Err(meta.error("unsupported table attribute, expected one of `name`, `if_not_exists`, `kind`, `rename_all`"))
+                }
+            })?;
+        }
+
+        Ok(attributes)
+    }
+}
+
+
+/// Convert a `Camel` identifier into `snake_case`.
+pub(crate) fn to_snake_case(ident: &str) -> String {
+    let mut snake = String::with_capacity(ident.len());
+    let mut previous: Option<char> = None;
+
+    for (index, c) in ident.char_indices() {
+        if c.is_uppercase() {
+            let next_is_lower = ident[index + c.len_utf8()..]
+                .chars()
+                .next()
+                .is_some_and(char::is_lowercase);
+            let starts_word = match previous {
+                Some(previous) => previous.is_lowercase() || previous.is_ascii_digit() || (previous.is_uppercase() && next_is_lower),
+                None => false,
+            };
+            if starts_word {
+                snake.push('_');
+            }
+            snake.extend(c.to_lowercase());
+        } else {
+            snake.push(c);
+        }
+        previous = Some(c);
+    }
+
+    snake
+}
+
+
+fn expand_table_field(
+    field: &syn::Field,
+    attributes: FieldAttributes,
+    rename_all: Option<RenameRule>,
+) -> Result<proc_macro2::TokenStream, syn::Error> {
+    let name = match attributes.name {
+        Some(name) => name.value(),
+        None => {
+            let ident = field
+                .ident
+                .as_ref()
+                .map(|ident| ident.unraw().to_string())
+                .ok_or_else(|| syn::Error::new(field.span(), "missing `name` in #[field(...)]"))?;
+            match rename_all {
+                Some(rule) => rule.apply(&ident),
+                None => ident,
+            }
+        },
+    };
+    let inner_type = option_inner_type(&field.ty);
+    let kind = match attributes.kind {
+        Some(kind) => quote!(#kind),
+        None => infer_kind(inner_type.unwrap_or(&field.ty))?,
+    };
+    let null = match attributes.null {
+        Some(null) => null.value,
+        None => inner_type.is_some(),
+    };
+    if let Some(primary_key) = &attributes.primary_key {
+        if null {
+            return Err(syn::Error::new(primary_key.span(), "a primary key column cannot be nullable"));
+        }
+    }
+    let primary_key = attributes.primary_key.is_some();
+    let unique = attributes.unique;
+
+    Ok(quote! {
+        ::ross_db::table::fields::TableField {
+            options: ::ross_db::table::fields::CommonFieldOptions {
+                name: #name.to_string(),
+                primary_key: #primary_key,
+                unique: #unique,
+                null: ::core::option::Option::Some(#null),
+                ..::core::default::Default::default()
+            },
+            kind: #kind,
+        }
+    })
+}
+
 
 pub fn expand_table_derive(input: &mut syn::DeriveInput) -> Result<proc_macro2::TokenStream, Vec<syn::Error>> {
-    todo!("Implement the conversion of DeriveInput into a TableDefn.");
-}
\ No newline at end of file
+    let fields = match &input.data {
+        syn::Data::Struct(syn::DataStruct { fields: syn::Fields::Named(fields), .. }) => &fields.named,
+        syn::Data::Enum(data) => return crate::enums::expand_enum_derive(input, data),
+        _ => {
+            return Err(vec![syn::Error::new(
+                input.span(),
+                "Table can only be derived for structs with named fields or unit-only enums",
+            )])
+        }
+    };
+
+    let attributes = TableAttributes::from_input(input).map_err(|err| vec![err])?;
+
+    let mut errors = Vec::new();
+    let mut table_fields = Vec::new();
+    let mut primary_key_seen = false;
+
+    for field in fields {
+        let field_attributes = match FieldAttributes::from_field(field) {
+            Ok(field_attributes) => field_attributes,
+            Err(err) => {
+                errors.push(err);
+                continue;
+            }
+        };
+
+        if field_attributes.skip.is_some() {
+            continue;
+        }
+
+        if let Some(primary_key) = &field_attributes.primary_key {
+            if primary_key_seen {
+                errors.push(syn::Error::new(primary_key.span(), "only one field can be marked as `primary_key`"));
+                continue;
+            }
+            primary_key_seen = true;
+        }
+
+        match expand_table_field(field, field_attributes, attributes.rename_all) {
+            Ok(table_field) => table_fields.push(table_field),
+            Err(err) => errors.push(err),
+        }
+    }
+
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+
+    let ident = &input.ident;
+    let table_name = match attributes.name {
+        Some(name) => name.value(),
+        None => to_snake_case(&ident.to_string()),
+    };
+    let if_not_exists = attributes.if_not_exists;
+    let kind = match attributes.kind {
+        Some(kind) => quote!(::core::option::Option::Some(::ross_db::table::fields::TableKind::#kind)),
+        None => quote!(::core::option::Option::None),
+    };
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics #ident #ty_generics #where_clause {
+            /// The table definition described by this struct's fields.
+            #[allow(clippy::needless_update)]
+            pub fn table_definition() -> ::ross_db::table::fields::TableDefn {
+                ::ross_db::table::fields::TableDefn {
+                    options: ::ross_db::table::fields::CommonTableOptions {
+                        name: #table_name.to_string(),
+                        if_not_exists: #if_not_exists,
+                        kind: #kind,
+                        ..::core::default::Default::default()
+                    },
+                    fields: vec![#(#table_fields),*],
+                }
+            }
+        }
+    })
+}
@@ -3,11 +3,199 @@ use ross_db::table::fields::Field;
 
 #[derive(Debug, Table)]
 pub struct Foo {
-    #[field(name="", kind=Field::Char)]
+    #[field(name="a", kind=Field::Char { max_length: Some(10) })]
     pub a: String
 }
 
 #[test]
 fn test_foo_generate_table() {
-    let f = Foo { a: "aa".to_string() };
-}
\ No newline at end of file
+    let observed = Foo::table_definition().to_string();
+    assert_eq!(observed, "CREATE TABLE foo (\n\ta CHAR(10) NOT NULL\n)");
+}
+
+#[derive(Debug, Table)]
+pub struct Post {
+    #[field(name="id")]
+    pub id: i64,
+    #[field(name="title")]
+    pub title: String,
+    #[field(name="views")]
+    pub views: i32,
+    #[field(name="published")]
+    pub published: bool,
+    #[field(name="tags")]
+    pub tags: Vec<String>,
+    #[field(name="thumbnail")]
+    pub thumbnail: Vec<u8>,
+}
+
+#[test]
+fn test_post_infers_field_kinds() {
+    let observed = Post::table_definition().to_string();
+    assert_eq!(
+        observed,
+        "CREATE TABLE post (\n\tid BIGINT NOT NULL,\n\ttitle TEXT NOT NULL,\n\tviews INTEGER NOT NULL,\n\tpublished BOOLEAN NOT NULL,\n\ttags TEXT[] NOT NULL,\n\tthumbnail BYTEA NOT NULL\n)"
+    );
+}
+
+#[derive(Debug, Table)]
+pub struct Comment {
+    #[field(name="id")]
+    pub id: i64,
+    #[field(name="body")]
+    pub body: Option<String>,
+    #[field(name="edited_at", kind=Field::Timestamp { with_timezone: true, precision: None })]
+    pub edited_at: Option<String>,
+    #[field(name="score", null=true)]
+    pub score: i32,
+    #[field(name="parent_id", null=false)]
+    pub parent_id: Option<i64>,
+}
+
+#[test]
+fn test_comment_optional_fields_are_nullable() {
+    let observed = Comment::table_definition().to_string();
+    assert_eq!(
+        observed,
+        "CREATE TABLE comment (\n\tid BIGINT NOT NULL,\n\tbody TEXT NULL,\n\tedited_at TIMESTAMP WITH TIME ZONE NULL,\n\tscore INTEGER NULL,\n\tparent_id BIGINT NOT NULL\n)"
+    );
+}
+
+#[derive(Debug, Table)]
+#[table(name = "user_accounts", if_not_exists)]
+pub struct UserAccount {
+    #[field(name="id")]
+    pub id: i64,
+}
+
+#[derive(Debug, Table)]
+#[table(kind = Local)]
+pub struct ImportBatchRow {
+    #[field(name="line")]
+    pub line: String,
+}
+
+#[test]
+fn test_table_attribute() {
+    let table = UserAccount::table_definition();
+    assert_eq!(table.options.name, "user_accounts");
+    assert!(table.options.if_not_exists);
+    assert!(table.options.kind.is_none());
+
+    let observed = table.to_string();
+    assert_eq!(observed, "CREATE TABLE IF NOT EXISTS user_accounts (\n\tid BIGINT NOT NULL\n)");
+
+    let table = ImportBatchRow::table_definition();
+    assert_eq!(table.options.name, "import_batch_row");
+    assert!(!table.options.if_not_exists);
+    assert!(matches!(table.options.kind, Some(ross_db::table::fields::TableKind::Local)));
+}
+
+#[derive(Debug, Table)]
+pub struct Author {
+    #[field(name="id", primary_key)]
+    pub id: i64,
+    #[field(name="email", unique)]
+    pub email: String,
+    #[field(name="nickname", unique, null=true)]
+    pub nickname: String,
+}
+
+#[test]
+fn test_author_field_flags() {
+    let observed = Author::table_definition().to_string();
+    assert_eq!(
+        observed,
+        "CREATE TABLE author (\n\tid BIGINT NOT NULL PRIMARY KEY,\n\temail TEXT NOT NULL UNIQUE,\n\tnickname TEXT NULL UNIQUE\n)"
+    );
+}
+
+#[derive(Debug, Table)]
+pub struct Tag {
+    #[field(primary_key)]
+    pub id: i32,
+    pub label: String,
+    #[field(name="colour")]
+    pub color: Option<String>,
+    pub r#type: String,
+}
+
+#[test]
+fn test_tag_column_names_default_to_field_names() {
+    let observed = Tag::table_definition().to_string();
+    assert_eq!(
+        observed,
+        "CREATE TABLE tag (\n\tid INTEGER NOT NULL PRIMARY KEY,\n\tlabel TEXT NOT NULL,\n\tcolour TEXT NULL,\n\ttype TEXT NOT NULL\n)"
+    );
+}
+
+#[derive(Debug, Table)]
+pub enum OrderStatus {
+    Pending,
+    InTransit,
+    Delivered,
+}
+
+#[derive(Debug, Table)]
+#[table(name="mood")]
+pub enum Feeling {
+    Happy,
+    Sad,
+}
+
+#[derive(Debug, Table)]
+#[table(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum Priority {
+    Low,
+    RightNow,
+}
+
+#[test]
+fn test_enum_generates_type() {
+    let observed = OrderStatus::enum_type().to_string();
+    assert_eq!(observed, "CREATE TYPE order_status AS ENUM ('pending', 'in_transit', 'delivered')");
+
+    let observed = Feeling::enum_type().to_string();
+    assert_eq!(observed, "CREATE TYPE mood AS ENUM ('happy', 'sad')");
+
+    let observed = Priority::enum_type().to_string();
+    assert_eq!(observed, "CREATE TYPE priority AS ENUM ('LOW', 'RIGHT_NOW')");
+}
+
+#[derive(Debug, Table)]
+pub struct Session {
+    pub token: String,
+    #[field(skip)]
+    pub last_seen: std::time::Instant,
+}
+
+#[test]
+fn test_skipped_field_is_not_a_column() {
+    let observed = Session::table_definition().to_string();
+    assert_eq!(observed, "CREATE TABLE session (\n\ttoken TEXT NOT NULL\n)");
+}
+
+#[allow(non_snake_case)]
+#[derive(Debug, Table)]
+#[table(rename_all = "snake_case")]
+pub struct LegacyImport {
+    pub RowNumber: i32,
+    #[field(name="Payload")]
+    pub SourcePayload: String,
+}
+
+#[derive(Debug, Table)]
+#[table(rename_all = "camelCase")]
+pub struct ApiEvent {
+    pub event_name: String,
+    pub received_at_ms: i64,
+}
+
+#[test]
+fn test_rename_all() {
+    let observed = LegacyImport::table_definition().to_string();
+    assert_eq!(observed, "CREATE TABLE legacy_import (\n\trow_number INTEGER NOT NULL,\n\tPayload TEXT NOT NULL\n)");
+
+    let observed = ApiEvent::table_definition().to_string();
+    assert_eq!(observed, "CREATE TABLE api_event (\n\teventName TEXT NOT NULL,\n\treceivedAtMs BIGINT NOT NULL\n)");
+}